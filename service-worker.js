@@ -0,0 +1,72 @@
+// Precaches the app shell so gymticks opens offline, including on the very first offline
+// launch after a visit. `cargo make build` writes the file list with content hashes to
+// public/precache-manifest.js, so each build gets a cache of its own and stale files are
+// never served after an update.
+importScripts('/public/precache-manifest.js');
+
+const CACHE_PREFIX = 'gymticks-precache-';
+
+// Short and stable: any changed hash gives a new cache name.
+const version = (() => {
+  let hash = 5381;
+  for (const [url, revision] of self.PRECACHE) {
+    for (const c of url + revision) {
+      hash = ((hash << 5) + hash + c.charCodeAt(0)) >>> 0;
+    }
+  }
+  return hash.toString(16);
+})();
+const CACHE = CACHE_PREFIX + version;
+
+// Every page of the app is index.html; routing happens in the app.
+const APP_SHELL = '/index.html';
+
+self.addEventListener('install', (event) => {
+  event.waitUntil((async () => {
+    const cache = await caches.open(CACHE);
+    await Promise.all(self.PRECACHE.map(async ([url, revision]) => {
+      // Skip the HTTP cache, or an old copy could end up under the new revision.
+      const response = await fetch(url, { cache: 'reload' });
+      if (!response.ok) {
+        throw new Error(`precaching ${url} (${revision}) failed: ${response.status}`);
+      }
+      await cache.put(url, response);
+    }));
+  })());
+  // No skipWaiting: the new version waits until the app asks for it, so a page is never
+  // served a mix of old and new files.
+});
+
+self.addEventListener('activate', (event) => {
+  event.waitUntil((async () => {
+    const names = await caches.keys();
+    await Promise.all(names
+      .filter((name) => name.startsWith(CACHE_PREFIX) && name !== CACHE)
+      .map((name) => caches.delete(name)));
+    await self.clients.claim();
+  })());
+});
+
+self.addEventListener('message', (event) => {
+  if (event.data === 'skip-waiting') {
+    self.skipWaiting();
+  }
+});
+
+self.addEventListener('fetch', (event) => {
+  const request = event.request;
+  const url = new URL(request.url);
+  if (request.method !== 'GET' || url.origin !== self.location.origin) {
+    return;
+  }
+
+  event.respondWith((async () => {
+    const cache = await caches.open(CACHE);
+    const cached = request.mode === 'navigate'
+      ? await cache.match(APP_SHELL)
+      // Stylesheets are linked with ?n to bust the HTTP cache, which the hashes now do.
+      : await cache.match(request, { ignoreSearch: true });
+
+    return cached || fetch(request);
+  })());
+});
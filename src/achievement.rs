@@ -0,0 +1,419 @@
+//! Achievements worked out from tick history: the first send of each grade, send count
+//! milestones, streaks of sessions with a send, and sending every color up in a section.
+//! Once unlocked they're kept, even if the routes that earned them are later stripped.
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{Locale, Text};
+use crate::{Msg, PersistedData, Route, RouteId, Settings, Tick, TickType};
+
+const SEND_MILESTONES: [u32; 7] = [1, 10, 50, 100, 250, 500, 1000];
+const STREAK_MILESTONES: [u32; 4] = [3, 5, 10, 25];
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum Achievement {
+    FirstSend(String),
+    Sends(u32),
+    /// Sessions in a row with at least one send each.
+    Streak(u32),
+    SectionColors(String),
+}
+
+impl Achievement {
+    pub fn title(&self, settings: &Settings) -> String {
+        let locale = settings.locale();
+
+        match self {
+            Achievement::FirstSend(grade) => {
+                let label = settings.grades.get(grade).map_or(grade, |g| &g.label);
+                locale.fill(Text::AchievementFirstSend, &[label])
+            }
+            Achievement::Sends(count) => locale.fill(Text::AchievementSends, &[count]),
+            Achievement::Streak(count) => locale.fill(Text::AchievementStreak, &[count]),
+            Achievement::SectionColors(section) => {
+                locale.fill(Text::AchievementSectionColors, &[section])
+            }
+        }
+    }
+
+    fn description(&self, locale: Locale) -> String {
+        match self {
+            Achievement::FirstSend(_) => locale.t(Text::AchievementFirstSendDescription),
+            Achievement::Sends(_) => locale.t(Text::AchievementSendsDescription),
+            Achievement::Streak(_) => locale.t(Text::AchievementStreakDescription),
+            Achievement::SectionColors(_) => locale.t(Text::AchievementSectionColorsDescription),
+        }
+        .to_string()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Unlocked {
+    /// A key into `Settings::climbers`, unset while nobody has been added.
+    pub climber: Option<String>,
+    pub achievement: Achievement,
+    /// When the tick that earned it was logged.
+    pub timestamp: i64,
+}
+
+/// The climber whose ticks these are, in the same terms as `Unlocked::climber`.
+struct History<'a> {
+    climber: Option<&'a String>,
+    routes: &'a IndexMap<RouteId, Route>,
+    settings: &'a Settings,
+}
+
+impl<'a> History<'a> {
+    fn is_mine(&self, tick: &Tick) -> bool {
+        self.climber.is_none() || self.settings.tick_climber(tick) == self.climber
+    }
+
+    /// The climber's sends, oldest first.
+    fn sends(&self) -> Vec<(&'a Route, &'a Tick)> {
+        self.routes
+            .values()
+            .flat_map(|route| route.ticks.iter().map(move |tick| (route, tick)))
+            .filter(|(_route, tick)| self.is_mine(tick) && matches!(tick.typ, TickType::Ascent))
+            .sorted_by_key(|(_route, tick)| tick.timestamp)
+            .collect()
+    }
+
+    /// For each climbing day, oldest first, whether it had a send and when the first one was.
+    fn sessions(&self) -> Vec<Option<i64>> {
+        self.routes
+            .values()
+            .flat_map(|route| &route.ticks)
+            .filter(|tick| self.is_mine(tick))
+            .map(|tick| (self.settings.climbing_day(tick.timestamp), tick))
+            .into_group_map()
+            .into_iter()
+            .sorted_by_key(|(day, _ticks)| *day)
+            .map(|(_day, ticks)| {
+                ticks
+                    .iter()
+                    .filter(|tick| matches!(tick.typ, TickType::Ascent))
+                    .map(|tick| tick.timestamp)
+                    .min()
+            })
+            .collect()
+    }
+
+    fn current_streak(&self) -> u32 {
+        self.sessions()
+            .iter()
+            .rev()
+            .take_while(|send| send.is_some())
+            .count() as u32
+    }
+
+    /// Every achievement earned so far, with when it was earned.
+    fn earned(&self) -> Vec<(Achievement, i64)> {
+        let mut earned = Vec::new();
+        let sends = self.sends();
+
+        let mut grades = std::collections::HashSet::new();
+        for (route, tick) in &sends {
+            if grades.insert(&route.grade) {
+                earned.push((Achievement::FirstSend(route.grade.clone()), tick.timestamp));
+            }
+        }
+
+        for milestone in SEND_MILESTONES.iter() {
+            if let Some((_route, tick)) = sends.get(*milestone as usize - 1) {
+                earned.push((Achievement::Sends(*milestone), tick.timestamp));
+            }
+        }
+
+        let mut streak = 0;
+        for send in self.sessions() {
+            match send {
+                Some(timestamp) => {
+                    streak += 1;
+                    if STREAK_MILESTONES.contains(&streak) {
+                        earned.push((Achievement::Streak(streak), timestamp));
+                    }
+                }
+                None => streak = 0,
+            }
+        }
+
+        for section in self.settings.sections.keys() {
+            if let Some(timestamp) = self.section_colors_sent(section, &sends) {
+                earned.push((Achievement::SectionColors(section.clone()), timestamp));
+            }
+        }
+
+        earned
+    }
+
+    /// When the last of the colors up in a section got its first send there, if they all have.
+    fn section_colors_sent(&self, section: &str, sends: &[(&Route, &Tick)]) -> Option<i64> {
+        let colors = self
+            .routes
+            .values()
+            .filter(|route| !route.retired && route.section == section)
+            .map(|route| &route.color)
+            .unique()
+            .collect::<Vec<_>>();
+
+        // One color is just one route, not much of an achievement.
+        if colors.len() < 2 {
+            return None;
+        }
+
+        colors
+            .iter()
+            .map(|color| {
+                sends
+                    .iter()
+                    .find(|(route, _tick)| route.section == section && route.color == **color)
+                    .map(|(_route, tick)| tick.timestamp)
+            })
+            .collect::<Option<Vec<i64>>>()?
+            .into_iter()
+            .max()
+    }
+}
+
+fn histories(persisted: &PersistedData) -> Vec<History<'_>> {
+    let settings = &persisted.settings;
+    let history = |climber| History {
+        climber,
+        routes: &persisted.routes,
+        settings,
+    };
+
+    if settings.climbers.is_empty() {
+        vec![history(None)]
+    } else {
+        settings
+            .climbers
+            .keys()
+            .map(|key| history(Some(key)))
+            .collect()
+    }
+}
+
+/// Saves newly earned achievements for every climber and returns them.
+pub fn record(persisted: &mut PersistedData) -> Vec<Achievement> {
+    if let Some(first) = persisted.settings.climbers.keys().next() {
+        give_unassigned(&mut persisted.achievements, first);
+    }
+
+    let new = histories(persisted)
+        .iter()
+        .flat_map(|history| {
+            history
+                .earned()
+                .into_iter()
+                .filter(|(achievement, _timestamp)| {
+                    !persisted.achievements.iter().any(|unlocked| {
+                        unlocked.climber.as_ref() == history.climber
+                            && unlocked.achievement == *achievement
+                    })
+                })
+                .map(|(achievement, timestamp)| Unlocked {
+                    climber: history.climber.cloned(),
+                    achievement,
+                    timestamp,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let achievements = new
+        .iter()
+        .map(|unlocked| unlocked.achievement.clone())
+        .collect();
+    persisted.achievements.extend(new);
+
+    achievements
+}
+
+/// Hands achievements earned before any climbers were added to the first climber, whose
+/// ticks those were, rather than earning them all over again.
+fn give_unassigned(achievements: &mut Vec<Unlocked>, first: &str) {
+    let owned: Vec<Achievement> = achievements
+        .iter()
+        .filter(|unlocked| unlocked.climber.as_deref() == Some(first))
+        .map(|unlocked| unlocked.achievement.clone())
+        .collect();
+
+    achievements
+        .retain(|unlocked| unlocked.climber.is_some() || !owned.contains(&unlocked.achievement));
+    for unlocked in achievements
+        .iter_mut()
+        .filter(|unlocked| unlocked.climber.is_none())
+    {
+        unlocked.climber = Some(first.to_string());
+    }
+}
+
+/// The active climber's achievements, newest first.
+fn unlocked(persisted: &PersistedData) -> Vec<&Unlocked> {
+    let settings = &persisted.settings;
+    let climber = settings
+        .active_climber
+        .as_ref()
+        .filter(|_| !settings.climbers.is_empty());
+
+    persisted
+        .achievements
+        .iter()
+        .filter(|unlocked| unlocked.climber.as_ref() == climber)
+        .sorted_by_key(|unlocked| std::cmp::Reverse(unlocked.timestamp))
+        .collect()
+}
+
+fn view_unlocked(unlocked: &Unlocked, settings: &Settings) -> Node<Msg> {
+    let locale = settings.locale();
+    let day = settings.climbing_day(unlocked.timestamp);
+
+    li![
+        C!["trophy"],
+        div![C!["trophy-title"], unlocked.achievement.title(settings)],
+        small![
+            C!["text-gray"],
+            format!(
+                "{} · {}",
+                unlocked.achievement.description(locale),
+                day.format(crate::DATE_FORMAT)
+            )
+        ],
+    ]
+}
+
+/// The latest few achievements, for the stats page.
+pub fn view_recent(persisted: &PersistedData, url: Url) -> Node<Msg> {
+    let settings = &persisted.settings;
+    let locale = settings.locale();
+    let unlocked = unlocked(persisted);
+
+    div![
+        C!["main card", "trophies"],
+        div![
+            C!["card-header"],
+            a![
+                C!["btn", "float-right"],
+                attrs! {
+                    At::Href => url
+                },
+                locale.t(Text::AllTrophies)
+            ],
+            div![C!["h5", "card-title"], locale.t(Text::Trophies)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.fill(Text::TrophiesCount, &[&unlocked.len()])
+            ],
+        ],
+        IF!(!unlocked.is_empty() => div![
+            C!["card-body"],
+            ul![unlocked
+                .iter()
+                .take(3)
+                .map(|unlocked| view_unlocked(unlocked, settings))
+                .collect::<Vec<Node<Msg>>>()]
+        ]),
+    ]
+}
+
+pub fn view_trophies(persisted: &PersistedData) -> Node<Msg> {
+    let settings = &persisted.settings;
+    let locale = settings.locale();
+    let unlocked = unlocked(persisted);
+    let climber = settings
+        .active_climber
+        .as_ref()
+        .filter(|_| !settings.climbers.is_empty());
+    let history = History {
+        climber,
+        routes: &persisted.routes,
+        settings,
+    };
+
+    let sends = history.sends().len() as u32;
+    let next_sends = SEND_MILESTONES.iter().find(|milestone| **milestone > sends);
+    let streak = history.current_streak();
+    let next_streak = STREAK_MILESTONES
+        .iter()
+        .find(|milestone| **milestone > streak);
+
+    let next_up = next_sends
+        .map(|milestone| (Achievement::Sends(*milestone), sends, *milestone))
+        .into_iter()
+        .chain(next_streak.map(|milestone| (Achievement::Streak(*milestone), streak, *milestone)));
+
+    div![
+        C!["main card", "trophies"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Trophies)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.t(Text::TrophiesCaption)
+            ],
+        ],
+        div![
+            C!["card-body"],
+            div![C!["h6"], locale.t(Text::NextUp)],
+            next_up
+                .map(|(achievement, done, needed)| div![
+                    C!["trophy", "locked"],
+                    div![C!["trophy-title"], achievement.title(settings)],
+                    div![
+                        C!["goal-progress"],
+                        progress![
+                            C!["progress"],
+                            attrs! {
+                                At::Value => done;
+                                At::Max => needed;
+                            }
+                        ],
+                        small![format!("{}/{}", done, needed)],
+                    ],
+                ])
+                .collect::<Vec<Node<Msg>>>(),
+            if unlocked.is_empty() {
+                p![C!["text-gray"], locale.t(Text::NoTrophies)]
+            } else {
+                ul![unlocked
+                    .iter()
+                    .map(|unlocked| view_unlocked(unlocked, settings))
+                    .collect::<Vec<Node<Msg>>>()]
+            },
+        ]
+    ]
+}
+
+/// The celebration shown when a tick unlocks something.
+pub fn view_celebration(achievements: &[Achievement], settings: &Settings, url: Url) -> Node<Msg> {
+    if achievements.is_empty() {
+        return empty![];
+    }
+
+    let locale = settings.locale();
+
+    div![
+        C!["toast", "toast-primary", "celebration"],
+        button![
+            C!["btn", "btn-clear", "float-right"],
+            ev(Ev::Click, |_| Msg::DismissCelebration)
+        ],
+        achievements
+            .iter()
+            .map(|achievement| div![
+                locale.fill(Text::AchievementUnlocked, &[&achievement.title(settings)])
+            ])
+            .collect::<Vec<Node<Msg>>>(),
+        a![
+            attrs! {
+                At::Href => url
+            },
+            ev(Ev::Click, |_| Msg::DismissCelebration),
+            locale.t(Text::AllTrophies)
+        ],
+    ]
+}
@@ -0,0 +1,565 @@
+//! The zip archive export: the persisted data as JSON, every tick as CSV, route photos, and
+//! a manifest listing each file with its size and CRC-32 so imports can be validated.
+//!
+//! Files are stored uncompressed. Photos are already compressed and the rest is small, so
+//! this keeps the writer and reader simple.
+
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone, Timelike};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::encryption::EncryptionError;
+use crate::i18n::{Locale, Text};
+use crate::{Route, RouteId, TickType};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+const FORMAT: &str = "gymticks-archive";
+const MANIFEST: &str = "manifest.json";
+const DATA: &str = "data.json";
+const TICKS: &str = "ticks.csv";
+const PHOTOS: &str = "photos/";
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const VERSION: u16 = 20;
+const UTF8_NAMES: u16 = 0x0800;
+const STORED: u16 = 0;
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    NotAnArchive,
+    Compressed(String),
+    BadManifest,
+    NewerVersion(u32),
+    MissingFile(String),
+    Damaged(String),
+    BadData,
+    Encryption(EncryptionError),
+}
+
+impl ArchiveError {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            ArchiveError::NotAnArchive => locale.t(Text::ArchiveNotAnArchive).to_string(),
+            ArchiveError::Compressed(name) => locale.fill(Text::ArchiveCompressed, &[name]),
+            ArchiveError::BadManifest => locale.t(Text::ArchiveBadManifest).to_string(),
+            ArchiveError::NewerVersion(version) => {
+                locale.fill(Text::ArchiveNewerVersion, &[version])
+            }
+            ArchiveError::MissingFile(name) => locale.fill(Text::ArchiveMissingFile, &[name]),
+            ArchiveError::Damaged(name) => locale.fill(Text::ArchiveDamaged, &[name]),
+            ArchiveError::BadData => locale.t(Text::ArchiveBadData).to_string(),
+            ArchiveError::Encryption(error) => encryption_message(error, locale),
+        }
+    }
+}
+
+fn encryption_message(error: &EncryptionError, locale: Locale) -> String {
+    match error {
+        EncryptionError::PassphraseRequired => {
+            locale.t(Text::EncryptionPassphraseRequired).to_string()
+        }
+        EncryptionError::WrongPassphrase => locale.t(Text::EncryptionWrongPassphrase).to_string(),
+        EncryptionError::UnsupportedVersion(version) => {
+            locale.fill(Text::EncryptionUnsupportedVersion, &[version])
+        }
+        EncryptionError::Damaged => locale.t(Text::EncryptionDamaged).to_string(),
+        EncryptionError::Failed => locale.t(Text::EncryptionFailed).to_string(),
+    }
+}
+
+pub struct ArchivePhoto {
+    pub route_id: String,
+    pub mime: String,
+    pub bytes: Vec<u8>,
+}
+
+pub struct Contents {
+    pub data_json: String,
+    pub photos: Vec<ArchivePhoto>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    format: String,
+    schema_version: u32,
+    /// Identifies the layout of `data.json`, which follows the LocalStorage format.
+    data_version: String,
+    created_at: i64,
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ManifestFile {
+    name: String,
+    size: u32,
+    crc32: String,
+}
+
+struct Entry {
+    name: String,
+    bytes: Vec<u8>,
+}
+
+/// Builds the archive. `now` is recorded in the manifest and as every file's modified time.
+pub fn write(
+    data_json: &str,
+    data_version: &str,
+    ticks_csv: &str,
+    photos: &[ArchivePhoto],
+    now: DateTime<Local>,
+) -> Vec<u8> {
+    let mut entries = vec![
+        Entry {
+            name: DATA.to_string(),
+            bytes: data_json.as_bytes().to_vec(),
+        },
+        Entry {
+            name: TICKS.to_string(),
+            bytes: ticks_csv.as_bytes().to_vec(),
+        },
+    ];
+
+    for photo in photos {
+        entries.push(Entry {
+            name: format!("{}{}.{}", PHOTOS, photo.route_id, extension(&photo.mime)),
+            bytes: photo.bytes.clone(),
+        });
+    }
+
+    let manifest = Manifest {
+        format: FORMAT.to_string(),
+        schema_version: SCHEMA_VERSION,
+        data_version: data_version.to_string(),
+        created_at: now.timestamp(),
+        files: entries
+            .iter()
+            .map(|entry| ManifestFile {
+                name: entry.name.clone(),
+                size: entry.bytes.len() as u32,
+                crc32: format!("{:08x}", crc32(&entry.bytes)),
+            })
+            .collect(),
+    };
+
+    // Goes first so it's easy to find when poking around the archive by hand.
+    entries.insert(
+        0,
+        Entry {
+            name: MANIFEST.to_string(),
+            bytes: serde_json::to_vec_pretty(&manifest).unwrap_or_default(),
+        },
+    );
+
+    write_zip(&entries, now.naive_local())
+}
+
+/// Reads and validates an archive, returning the data JSON and photos.
+pub fn read(bytes: &[u8]) -> Result<Contents, ArchiveError> {
+    let entries = read_zip(bytes)?;
+
+    let file = |name: &str| entries.iter().find(|entry| entry.name == name);
+
+    let manifest: Manifest = file(MANIFEST)
+        .and_then(|entry| serde_json::from_slice(&entry.bytes).ok())
+        .ok_or(ArchiveError::BadManifest)?;
+
+    if manifest.format != FORMAT {
+        return Err(ArchiveError::BadManifest);
+    }
+    if manifest.schema_version > SCHEMA_VERSION {
+        return Err(ArchiveError::NewerVersion(manifest.schema_version));
+    }
+    if !manifest.files.iter().any(|listed| listed.name == DATA) {
+        return Err(ArchiveError::MissingFile(DATA.to_string()));
+    }
+
+    for listed in &manifest.files {
+        let entry =
+            file(&listed.name).ok_or_else(|| ArchiveError::MissingFile(listed.name.clone()))?;
+
+        if entry.bytes.len() as u32 != listed.size
+            || format!("{:08x}", crc32(&entry.bytes)) != listed.crc32.to_lowercase()
+        {
+            return Err(ArchiveError::Damaged(listed.name.clone()));
+        }
+    }
+
+    let data_json = file(DATA)
+        .and_then(|entry| String::from_utf8(entry.bytes.clone()).ok())
+        .ok_or_else(|| ArchiveError::Damaged(DATA.to_string()))?;
+
+    // Only photos listed in the manifest have been checked.
+    let photos = manifest
+        .files
+        .iter()
+        .filter_map(|listed| {
+            let file_name = listed.name.strip_prefix(PHOTOS)?;
+            let (route_id, extension) = file_name.rsplit_once('.')?;
+
+            Some(ArchivePhoto {
+                route_id: route_id.to_string(),
+                mime: mime(extension).to_string(),
+                bytes: file(&listed.name)?.bytes.clone(),
+            })
+        })
+        .collect();
+
+    Ok(Contents { data_json, photos })
+}
+
+/// Every tick, one per row, for reading in a spreadsheet.
+pub fn ticks_csv(routes: &IndexMap<RouteId, Route>) -> String {
+    let mut csv =
+        String::from("route_id,title,section,grade,color,retired,type,time,styles,note\r\n");
+
+    for (route_id, route) in routes {
+        for tick in &route.ticks {
+            let row = [
+                route_id.to_string(),
+                route.title.clone(),
+                route.section.clone(),
+                route.grade.clone(),
+                route.color.clone(),
+                route.retired.to_string(),
+                match tick.typ {
+                    TickType::Ascent => "send",
+                    TickType::Attempt => "attempt",
+                }
+                .to_string(),
+                Local
+                    .timestamp_opt(tick.timestamp, 0)
+                    .single()
+                    .map_or_else(String::new, |time| time.to_rfc3339()),
+                tick.styles.join(" "),
+                tick.note.clone(),
+            ];
+
+            csv.push_str(
+                &row.iter()
+                    .map(|field| csv_field(field))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+            csv.push_str("\r\n");
+        }
+    }
+
+    csv
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn extension(mime: &str) -> &'static str {
+    match mime {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/heic" => "heic",
+        _ => "bin",
+    }
+}
+
+fn mime(extension: &str) -> &'static str {
+    match extension {
+        "jpg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "heic" => "image/heic",
+        _ => "application/octet-stream",
+    }
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            c = if c & 1 == 1 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            bit += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        CRC_TABLE[((crc ^ u32::from(*byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn dos_time(time: NaiveDateTime) -> (u16, u16) {
+    let dos_time = (time.hour() << 11) | (time.minute() << 5) | (time.second() / 2);
+    let dos_date = (((time.year() - 1980).max(0) as u32) << 9) | (time.month() << 5) | time.day();
+
+    (dos_time as u16, dos_date as u16)
+}
+
+fn write_zip(entries: &[Entry], now: NaiveDateTime) -> Vec<u8> {
+    let (time, date) = dos_time(now);
+
+    let mut zip = Vec::new();
+    let mut central = Vec::new();
+
+    for entry in entries {
+        let offset = zip.len() as u32;
+        let crc = crc32(&entry.bytes);
+        let size = entry.bytes.len() as u32;
+        let name = entry.name.as_bytes();
+
+        put_u32(&mut zip, LOCAL_HEADER);
+        put_u16(&mut zip, VERSION);
+        put_u16(&mut zip, UTF8_NAMES);
+        put_u16(&mut zip, STORED);
+        put_u16(&mut zip, time);
+        put_u16(&mut zip, date);
+        put_u32(&mut zip, crc);
+        put_u32(&mut zip, size);
+        put_u32(&mut zip, size);
+        put_u16(&mut zip, name.len() as u16);
+        put_u16(&mut zip, 0);
+        zip.extend_from_slice(name);
+        zip.extend_from_slice(&entry.bytes);
+
+        put_u32(&mut central, CENTRAL_HEADER);
+        put_u16(&mut central, VERSION);
+        put_u16(&mut central, VERSION);
+        put_u16(&mut central, UTF8_NAMES);
+        put_u16(&mut central, STORED);
+        put_u16(&mut central, time);
+        put_u16(&mut central, date);
+        put_u32(&mut central, crc);
+        put_u32(&mut central, size);
+        put_u32(&mut central, size);
+        put_u16(&mut central, name.len() as u16);
+        put_u16(&mut central, 0);
+        put_u16(&mut central, 0);
+        put_u16(&mut central, 0);
+        put_u16(&mut central, 0);
+        put_u32(&mut central, 0);
+        put_u32(&mut central, offset);
+        central.extend_from_slice(name);
+    }
+
+    let central_offset = zip.len() as u32;
+    let central_size = central.len() as u32;
+    zip.extend_from_slice(&central);
+
+    put_u32(&mut zip, END_OF_CENTRAL_DIRECTORY);
+    put_u16(&mut zip, 0);
+    put_u16(&mut zip, 0);
+    put_u16(&mut zip, entries.len() as u16);
+    put_u16(&mut zip, entries.len() as u16);
+    put_u32(&mut zip, central_size);
+    put_u32(&mut zip, central_offset);
+    put_u16(&mut zip, 0);
+
+    zip
+}
+
+fn read_zip(bytes: &[u8]) -> Result<Vec<Entry>, ArchiveError> {
+    // The end of central directory record is at least 22 bytes and may be followed by a
+    // comment, so search backwards for its signature.
+    let end = (0..=bytes.len().saturating_sub(22))
+        .rev()
+        .find(|at| get_u32(bytes, *at) == Some(END_OF_CENTRAL_DIRECTORY))
+        .ok_or(ArchiveError::NotAnArchive)?;
+
+    let count = get_u16(bytes, end + 10).ok_or(ArchiveError::NotAnArchive)?;
+    let mut at = get_u32(bytes, end + 16).ok_or(ArchiveError::NotAnArchive)? as usize;
+
+    let mut entries = Vec::new();
+
+    for _ in 0..count {
+        if get_u32(bytes, at) != Some(CENTRAL_HEADER) {
+            return Err(ArchiveError::NotAnArchive);
+        }
+
+        let field = |offset: usize| get_u16(bytes, at + offset).map(usize::from);
+        let method = get_u16(bytes, at + 10).ok_or(ArchiveError::NotAnArchive)?;
+        let size = get_u32(bytes, at + 20).ok_or(ArchiveError::NotAnArchive)? as usize;
+        let name_len = field(28).ok_or(ArchiveError::NotAnArchive)?;
+        let extra_len = field(30).ok_or(ArchiveError::NotAnArchive)?;
+        let comment_len = field(32).ok_or(ArchiveError::NotAnArchive)?;
+        let offset = get_u32(bytes, at + 42).ok_or(ArchiveError::NotAnArchive)? as usize;
+
+        let name = bytes
+            .get(at + 46..at + 46 + name_len)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .ok_or(ArchiveError::NotAnArchive)?;
+
+        if method != STORED {
+            return Err(ArchiveError::Compressed(name));
+        }
+
+        if get_u32(bytes, offset) != Some(LOCAL_HEADER) {
+            return Err(ArchiveError::Damaged(name));
+        }
+
+        // The local header's name and extra field can differ in length from the central one.
+        let local_name_len = get_u16(bytes, offset + 26).ok_or(ArchiveError::NotAnArchive)?;
+        let local_extra_len = get_u16(bytes, offset + 28).ok_or(ArchiveError::NotAnArchive)?;
+        let start = offset + 30 + usize::from(local_name_len) + usize::from(local_extra_len);
+
+        // Sizes come from the file, so a damaged one mustn't overflow on 32-bit targets.
+        let data = start
+            .checked_add(size)
+            .and_then(|end| bytes.get(start..end))
+            .ok_or_else(|| ArchiveError::Damaged(name.clone()))?;
+
+        entries.push(Entry {
+            name,
+            bytes: data.to_vec(),
+        });
+
+        at += 46 + name_len + extra_len + comment_len;
+    }
+
+    Ok(entries)
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn get_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    let mut value = [0; 2];
+    value.copy_from_slice(bytes.get(at..at.checked_add(2)?)?);
+    Some(u16::from_le_bytes(value))
+}
+
+fn get_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    let mut value = [0; 4];
+    value.copy_from_slice(bytes.get(at..at.checked_add(4)?)?);
+    Some(u32::from_le_bytes(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA_JSON: &str = r#"{"routes":{}}"#;
+    const CSV: &str = "route_id,title\r\n";
+
+    fn photo(route_id: &str, bytes: &[u8]) -> ArchivePhoto {
+        ArchivePhoto {
+            route_id: route_id.to_string(),
+            mime: "image/jpeg".to_string(),
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    fn archive(photos: &[ArchivePhoto]) -> Vec<u8> {
+        write(DATA_JSON, "gymticks-11", CSV, photos, Local::now())
+    }
+
+    /// Where the central directory starts, read from the end of central directory record.
+    fn central_offset(zip: &[u8]) -> usize {
+        get_u32(zip, zip.len() - 6).unwrap() as usize
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn round_trip_without_photos() {
+        let contents = read(&archive(&[])).unwrap();
+
+        assert_eq!(contents.data_json, DATA_JSON);
+        assert!(contents.photos.is_empty());
+    }
+
+    #[test]
+    fn round_trip_with_photos() {
+        let photos = [photo("route-a", &[0xff, 0xd8, 0xff]), photo("route-b", &[])];
+        let contents = read(&archive(&photos)).unwrap();
+
+        assert_eq!(contents.data_json, DATA_JSON);
+        assert_eq!(contents.photos.len(), 2);
+        for (read, written) in contents.photos.iter().zip(photos.iter()) {
+            assert_eq!(read.route_id, written.route_id);
+            assert_eq!(read.mime, written.mime);
+            assert_eq!(read.bytes, written.bytes);
+        }
+    }
+
+    #[test]
+    fn truncated_archive_is_an_error() {
+        let zip = archive(&[photo("route-a", &[1, 2, 3])]);
+
+        for len in 0..zip.len() {
+            assert!(read(&zip[..len]).is_err(), "truncated to {} bytes", len);
+        }
+    }
+
+    #[test]
+    fn huge_sizes_in_central_directory_are_an_error() {
+        let zip = archive(&[photo("route-a", &[1, 2, 3])]);
+        let central = central_offset(&zip);
+
+        // The first entry's compressed size, and its local header offset.
+        for field in [20, 42].iter() {
+            let mut damaged = zip.clone();
+            damaged[central + field..central + field + 4].copy_from_slice(&[0xff; 4]);
+
+            assert!(read(&damaged).is_err(), "field at {}", field);
+        }
+    }
+
+    #[test]
+    fn corrupted_central_directory_does_not_panic() {
+        let zip = archive(&[photo("route-a", &[1, 2, 3])]);
+
+        for at in central_offset(&zip)..zip.len() {
+            for value in [0x00, 0xff].iter() {
+                let mut damaged = zip.clone();
+                damaged[at] = *value;
+
+                // Some bytes, like timestamps, don't matter, so only check this returns.
+                let _ = read(&damaged);
+            }
+        }
+    }
+
+    #[test]
+    fn damaged_file_is_reported() {
+        let mut zip = archive(&[]);
+        let data_at = zip
+            .windows(DATA_JSON.len())
+            .position(|window| window == DATA_JSON.as_bytes())
+            .unwrap();
+        zip[data_at] = b'[';
+
+        match read(&zip) {
+            Err(ArchiveError::Damaged(name)) => assert_eq!(name, DATA),
+            _ => panic!("expected the data file to be damaged"),
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Rolling snapshots of the persisted data, kept in LocalStorage next to it. A snapshot is
+//! taken the first time the app opens each day and before anything replaces the data
+//! wholesale, so a bad import or a bug can be rolled back from the backups page.
+//!
+//! Each snapshot is stored under its own key and an index lists them, so restoring one
+//! doesn't mean reading them all. Snapshots share LocalStorage's quota with the data, so
+//! they give way to it when storage fills up, and the user is told when that happens.
+
+use chrono::{Local, TimeZone, Utc};
+use seed::prelude::web_storage::WebStorageError;
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{Locale, Text};
+use crate::{util, Msg, PersistedData, STORAGE_KEY};
+
+const INDEX_KEY: &str = "gymticks-backups";
+const SNAPSHOT_KEY_PREFIX: &str = "gymticks-backup-";
+
+/// Daily snapshots cover the last week the app was used.
+const KEEP_DAILY: usize = 7;
+/// Snapshots taken before imports and restores.
+const KEEP_OTHER: usize = 5;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Reason {
+    Daily,
+    BeforeImport,
+    BeforeRestore,
+}
+
+impl Reason {
+    fn label(&self) -> Text {
+        match self {
+            Reason::Daily => Text::BackupDaily,
+            Reason::BeforeImport => Text::BackupBeforeImport,
+            Reason::BeforeRestore => Text::BackupBeforeRestore,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Snapshot {
+    pub key: String,
+    pub created_at: i64,
+    pub reason: Reason,
+    /// The storage key of the data when the snapshot was taken. Snapshots from another
+    /// version can't be restored.
+    pub data_version: String,
+    pub routes: usize,
+    pub ticks: usize,
+}
+
+impl Snapshot {
+    fn restorable(&self) -> bool {
+        self.data_version == STORAGE_KEY
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum SaveOutcome {
+    Saved,
+    /// Saved, but only after dropping snapshots to make room.
+    DroppedSnapshots,
+    Failed,
+}
+
+/// All snapshots, newest first.
+pub fn list() -> Vec<Snapshot> {
+    LocalStorage::get(INDEX_KEY).unwrap_or_default()
+}
+
+/// Takes a snapshot of `persisted` and prunes old ones, returning the updated list and whether
+/// storage was too full to keep every snapshot. There's nothing worth keeping in an empty gym,
+/// so that is never snapshotted.
+pub fn take(persisted: &PersistedData, reason: Reason) -> (Vec<Snapshot>, bool) {
+    let mut snapshots = list();
+
+    if persisted.routes.is_empty() {
+        return (snapshots, false);
+    }
+
+    let created_at = Utc::now().timestamp();
+    let snapshot = Snapshot {
+        key: format!("{}{}-{:?}", SNAPSHOT_KEY_PREFIX, created_at, reason),
+        created_at,
+        reason,
+        data_version: STORAGE_KEY.to_string(),
+        routes: persisted.routes.len(),
+        ticks: persisted
+            .routes
+            .values()
+            .map(|route| route.ticks.len())
+            .sum(),
+    };
+
+    // When storage is full, drop the oldest snapshots to make room rather than failing.
+    let mut dropped = false;
+    while LocalStorage::insert(&snapshot.key, persisted).is_err() {
+        dropped = true;
+        match snapshots.pop() {
+            Some(oldest) => remove_snapshot(&oldest.key),
+            None => {
+                save_index(&snapshots);
+                return (snapshots, dropped);
+            }
+        }
+    }
+
+    snapshots.insert(0, snapshot);
+    prune(&mut snapshots);
+    save_index(&snapshots);
+
+    (snapshots, dropped)
+}
+
+/// Saves the data, dropping the oldest snapshots from `snapshots` while storage is too full
+/// for it.
+pub fn save(persisted: &PersistedData, snapshots: &mut Vec<Snapshot>) -> SaveOutcome {
+    let mut saved = SaveOutcome::Saved;
+
+    loop {
+        match LocalStorage::insert(STORAGE_KEY, persisted) {
+            Err(WebStorageError::InsertError(_)) => match snapshots.pop() {
+                Some(oldest) => {
+                    remove_snapshot(&oldest.key);
+                    save_index(snapshots);
+                    saved = SaveOutcome::DroppedSnapshots;
+                }
+                None => return SaveOutcome::Failed,
+            },
+            Err(_) => return SaveOutcome::Failed,
+            Ok(()) => return saved,
+        }
+    }
+}
+
+/// Takes the climbing day's snapshot unless there already is one.
+pub fn take_daily(persisted: &PersistedData) -> (Vec<Snapshot>, bool) {
+    let settings = &persisted.settings;
+    let today = settings.today();
+    let snapshots = list();
+
+    let taken_today = snapshots.iter().any(|snapshot| {
+        snapshot.reason == Reason::Daily && settings.climbing_day(snapshot.created_at) == today
+    });
+
+    if taken_today {
+        (snapshots, false)
+    } else {
+        take(persisted, Reason::Daily)
+    }
+}
+
+pub fn load(key: &str) -> Option<PersistedData> {
+    list()
+        .iter()
+        .find(|snapshot| snapshot.key == key && snapshot.restorable())
+        .and_then(|snapshot| LocalStorage::get(&snapshot.key).ok())
+}
+
+pub fn delete(key: &str) -> Vec<Snapshot> {
+    let mut snapshots = list();
+    snapshots.retain(|snapshot| snapshot.key != key);
+    remove_snapshot(key);
+    save_index(&snapshots);
+
+    snapshots
+}
+
+fn prune(snapshots: &mut Vec<Snapshot>) {
+    let mut daily = 0;
+    let mut other = 0;
+
+    snapshots.retain(|snapshot| {
+        let (count, limit) = if snapshot.reason == Reason::Daily {
+            (&mut daily, KEEP_DAILY)
+        } else {
+            (&mut other, KEEP_OTHER)
+        };
+
+        *count += 1;
+        if *count > limit {
+            remove_snapshot(&snapshot.key);
+            false
+        } else {
+            true
+        }
+    });
+}
+
+fn remove_snapshot(key: &str) {
+    // Removing a key that's already gone isn't a problem.
+    let _ = LocalStorage::remove(key);
+}
+
+fn save_index(snapshots: &[Snapshot]) {
+    // The index is tiny, and losing it only hides snapshots, so a failure is ignored.
+    let _ = LocalStorage::insert(INDEX_KEY, &snapshots);
+}
+
+pub fn view_save_failed(save_failed: bool, locale: Locale) -> Node<Msg> {
+    if !save_failed {
+        return empty![];
+    }
+
+    div![
+        C!["toast", "toast-error", "update-prompt"],
+        locale.t(Text::SaveFailed)
+    ]
+}
+
+pub fn view_snapshots_dropped(dropped: bool, locale: Locale) -> Node<Msg> {
+    if !dropped {
+        return empty![];
+    }
+
+    div![
+        C!["toast", "toast-warning", "update-prompt"],
+        button![
+            C!["btn", "btn-clear", "float-right"],
+            ev(Ev::Click, |_| Msg::DismissSnapshotsDropped)
+        ],
+        locale.t(Text::SnapshotsDropped)
+    ]
+}
+
+pub fn view_backups(snapshots: &[Snapshot], restore_failed: bool, locale: Locale) -> Node<Msg> {
+    let now = Utc::now();
+
+    let rows = snapshots
+        .iter()
+        .map(|snapshot| {
+            let key = snapshot.key.clone();
+            let delete_key = snapshot.key.clone();
+            let created_at = Utc
+                .timestamp_opt(snapshot.created_at, 0)
+                .single()
+                .unwrap_or_default();
+
+            li![div![
+                C!["view"],
+                label![
+                    div![locale.t(snapshot.reason.label())],
+                    small![
+                        C!["text-gray"],
+                        format!(
+                            "{} · {} · {}",
+                            created_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                            util::time_diff_in_words(created_at, now, locale),
+                            locale.fill(Text::BackupCounts, &[&snapshot.routes, &snapshot.ticks]),
+                        )
+                    ],
+                ],
+                IF!(snapshot.restorable() => button![
+                    C!["tick-button btn btn-sm btn-primary"],
+                    ev(Ev::Click, move |_| Msg::RestoreBackup(key)),
+                    locale.t(Text::Restore)
+                ]),
+                button![
+                    C!["tick-button btn btn-sm"],
+                    ev(Ev::Click, move |_| Msg::DeleteBackup(delete_key)),
+                    locale.t(Text::Delete)
+                ],
+            ]]
+        })
+        .collect::<Vec<Node<Msg>>>();
+
+    div![
+        C!["main card"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Backups)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.t(Text::BackupsCaption)
+            ],
+        ],
+        if rows.is_empty() {
+            div![C!["card-body", "text-gray"], locale.t(Text::NoBackups)]
+        } else {
+            ul![C!["route-list"], rows]
+        },
+        IF!(restore_failed => div![
+            C!["card-footer"],
+            div![C!["toast", "toast-error"], locale.t(Text::BackupUnreadable)]
+        ]),
+    ]
+}
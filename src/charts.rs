@@ -0,0 +1,228 @@
+use chrono::Duration;
+use indexmap::IndexMap;
+use itertools::Itertools;
+use seed::{prelude::*, *};
+
+use crate::i18n::Text;
+use crate::{Msg, Route, RouteId, Settings, TickType};
+
+const WEEKS: i64 = 26;
+const AVERAGE_WEEKS: usize = 4;
+
+const WIDTH: f64 = 300.0;
+const HEIGHT: f64 = 120.0;
+const LEFT: f64 = 30.0;
+const TOP: f64 = 6.0;
+const BOTTOM: f64 = 6.0;
+
+#[derive(Default, Clone, Copy)]
+pub struct WeekProgress {
+    /// Rank of the hardest grade sent that week.
+    pub hardest: Option<usize>,
+    /// Mean rank of everything sent in the trailing `AVERAGE_WEEKS` weeks.
+    pub average: Option<f64>,
+}
+
+/// The grades of a discipline (grade group), easiest first.
+fn grades_in_group<'a>(settings: &'a Settings, group: &str) -> Vec<&'a String> {
+    settings
+        .grades
+        .iter()
+        .filter(|(_k, grade)| grade.group == group)
+        .sorted_by_key(|(_k, grade)| grade.sort)
+        .map(|(key, _grade)| key)
+        .collect()
+}
+
+fn grade_label<'a>(settings: &'a Settings, key: &'a str) -> &'a str {
+    settings
+        .grades
+        .get(key)
+        .map_or(key, |grade| grade.label.as_str())
+}
+
+/// Weekly progress in one discipline for the last `WEEKS` weeks, oldest first. Grades are
+/// ranked by their position in the discipline so that grade systems with gaps in `sort`
+/// still plot evenly.
+pub fn progress_by_week(
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+    group: &str,
+) -> Vec<WeekProgress> {
+    let grades = grades_in_group(settings, group);
+    let start = settings.week_start(settings.today() - Duration::weeks(WEEKS - 1));
+
+    let mut sends: Vec<Vec<usize>> = vec![vec![]; WEEKS as usize];
+
+    for route in routes.values() {
+        let rank = match grades.iter().position(|grade| **grade == route.grade) {
+            Some(rank) => rank,
+            None => continue,
+        };
+
+        for tick in &route.ticks {
+            if let TickType::Attempt = tick.typ {
+                continue;
+            }
+
+            let week =
+                (settings.week_start(settings.climbing_day(tick.timestamp)) - start).num_weeks();
+
+            if (0..WEEKS).contains(&week) {
+                sends[week as usize].push(rank);
+            }
+        }
+    }
+
+    (0..sends.len())
+        .map(|week| {
+            let window = &sends[(week + 1).saturating_sub(AVERAGE_WEEKS)..=week];
+            let count: usize = window.iter().map(Vec::len).sum();
+            let total: usize = window.iter().flatten().sum();
+
+            WeekProgress {
+                hardest: sends[week].iter().max().copied(),
+                average: Some(count)
+                    .filter(|count| *count > 0)
+                    .map(|count| total as f64 / count as f64),
+            }
+        })
+        .collect()
+}
+
+/// Line charts of the hardest send per week and a moving average send grade, one per
+/// discipline that has sends in the charted period.
+pub fn view_progress(routes: &IndexMap<RouteId, Route>, settings: &Settings) -> Node<Msg> {
+    let locale = settings.locale();
+
+    let charts = settings
+        .grades
+        .values()
+        .map(|grade| grade.group.as_str())
+        .unique()
+        .filter_map(|group| {
+            let progress = progress_by_week(routes, settings, group);
+
+            if progress.iter().all(|week| week.hardest.is_none()) {
+                return None;
+            }
+
+            Some(view_chart(settings, group, &progress))
+        })
+        .collect::<Vec<Node<Msg>>>();
+
+    if charts.is_empty() {
+        return empty![];
+    }
+
+    div![
+        C!["main card", "progress-chart"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Progress)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.fill(Text::LastWeeks, &[&WEEKS])
+            ],
+        ],
+        div![
+            C!["card-body"],
+            charts,
+            small![
+                span![C!["legend-hardest"], locale.t(Text::HardestSend)],
+                " ",
+                span![
+                    C!["legend-average"],
+                    locale.fill(Text::AverageGrade, &[&AVERAGE_WEEKS])
+                ],
+            ],
+        ]
+    ]
+}
+
+fn view_chart(settings: &Settings, group: &str, progress: &[WeekProgress]) -> Node<Msg> {
+    let grades = grades_in_group(settings, group);
+    let top_rank = grades.len().saturating_sub(1).max(1) as f64;
+
+    let x = |week: usize| LEFT + week as f64 * (WIDTH - LEFT) / (WEEKS - 1) as f64;
+    let y = |rank: f64| TOP + (1.0 - rank / top_rank) * (HEIGHT - TOP - BOTTOM);
+
+    let points = |values: Vec<(usize, f64)>| {
+        values
+            .into_iter()
+            .map(|(week, rank)| format!("{:.1},{:.1}", x(week), y(rank)))
+            .join(" ")
+    };
+
+    let hardest: Vec<(usize, f64)> = progress
+        .iter()
+        .enumerate()
+        .filter_map(|(week, p)| p.hardest.map(|rank| (week, rank as f64)))
+        .collect();
+    let average = points(
+        progress
+            .iter()
+            .enumerate()
+            .filter_map(|(week, p)| p.average.map(|rank| (week, rank)))
+            .collect(),
+    );
+
+    // Disciplines don't have names of their own, so head each chart with its grade range.
+    let heading = format!(
+        "{}\u{2013}{}",
+        grades.first().map_or("", |key| grade_label(settings, key)),
+        grades.last().map_or("", |key| grade_label(settings, key))
+    );
+
+    let label = |rank: usize| {
+        grades.get(rank).map(|key| {
+            let text = grade_label(settings, key);
+
+            text![
+                attrs! {
+                    At::X => LEFT - 4.0;
+                    At::Y => y(rank as f64);
+                    At::TextAnchor => "end";
+                    At::DominantBaseline => "middle";
+                },
+                text
+            ]
+        })
+    };
+
+    div![
+        div![C!["chart-heading"], heading],
+        svg![
+            attrs! {
+                At::ViewBox => format!("0 0 {} {}", WIDTH, HEIGHT);
+            },
+            label(0),
+            label(grades.len() / 2),
+            label(grades.len().saturating_sub(1)),
+            polyline![
+                C!["average"],
+                attrs! {
+                    At::Points => average;
+                }
+            ],
+            polyline![
+                C!["hardest"],
+                attrs! {
+                    At::Points => points(hardest.clone());
+                }
+            ],
+            // A lone week has no line to draw, so mark every week with a dot.
+            hardest
+                .iter()
+                .map(|(week, rank)| circle![
+                    C!["hardest"],
+                    attrs! {
+                        At::Cx => format!("{:.1}", x(*week));
+                        At::Cy => format!("{:.1}", y(*rank));
+                        At::R => 2;
+                    }
+                ])
+                .collect::<Vec<Node<Msg>>>(),
+        ]
+    ]
+}
@@ -0,0 +1,162 @@
+use indexmap::IndexMap;
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Text;
+use crate::{Msg, Route, RouteId, Settings, TickType};
+
+/// Someone sharing the app on this device. Each tick records who climbed it, while routes
+/// and gym settings are shared.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Climber {
+    pub name: String,
+}
+
+/// A climber waiting for the user to confirm their removal.
+pub struct Removal {
+    pub key: String,
+    /// How many ticks the climber has.
+    pub ticks: usize,
+    /// Who gets those ticks. `None` only once no other climbers are left, which puts the ticks
+    /// back to how they were before there were any climbers.
+    pub heir: Option<String>,
+}
+
+impl Removal {
+    pub fn new(routes: &IndexMap<RouteId, Route>, settings: &Settings, key: String) -> Self {
+        let ticks = routes
+            .values()
+            .flat_map(|route| route.ticks.iter())
+            .filter(|tick| settings.tick_climber(tick) == Some(&key))
+            .count();
+        let heir = settings
+            .climbers
+            .keys()
+            .find(|other| **other != key)
+            .cloned();
+
+        Removal { key, ticks, heir }
+    }
+}
+
+/// Removes the climber and gives their ticks to the heir. Their ticks are given away
+/// explicitly, since ticks without a climber count for whoever is first.
+pub fn remove(routes: &mut IndexMap<RouteId, Route>, settings: &mut Settings, removal: Removal) {
+    for tick in routes.values_mut().flat_map(|route| route.ticks.iter_mut()) {
+        if settings.tick_climber(tick) == Some(&removal.key) {
+            tick.climber = removal.heir.clone();
+        }
+    }
+
+    settings.climbers.shift_remove(&removal.key);
+    if settings.active_climber.as_ref() == Some(&removal.key) {
+        settings.active_climber = settings.climbers.keys().next().cloned();
+    }
+}
+
+/// Asks whether to remove a climber and who gets their ticks.
+pub fn view_removal(removal: &Removal, settings: &Settings) -> Node<Msg> {
+    let locale = settings.locale();
+    let name = settings
+        .climbers
+        .get(&removal.key)
+        .map_or("", |climber| climber.name.as_str());
+    let others = settings
+        .climbers
+        .iter()
+        .filter(|(key, _climber)| **key != removal.key);
+
+    div![
+        C!["toast", "climber-removal"],
+        p![locale.fill(Text::RemoveClimberPrompt, &[&name])],
+        IF!(removal.ticks > 0 && removal.heir.is_none() => p![
+            locale.fill(Text::ClimberTicksUnassigned, &[&removal.ticks])
+        ]),
+        IF!(removal.ticks > 0 && removal.heir.is_some() => div![
+            C!["form-group"],
+            label![
+                C!["form-label"],
+                locale.fill(Text::ClimberTicksGoTo, &[&removal.ticks])
+            ],
+            select![
+                C!["form-select"],
+                others
+                    .map(|(key, climber)| option![
+                        attrs! {
+                            At::Value => key;
+                            At::Selected => (removal.heir.as_ref() == Some(key)).as_at_value();
+                        },
+                        climber.name.as_str()
+                    ])
+                    .collect::<Vec<Node<Msg>>>(),
+                input_ev(Ev::Change, Msg::ChooseClimberHeir),
+            ],
+        ]),
+        button![
+            C!["btn", "btn-sm", "btn-error"],
+            ev(Ev::Click, |_| Msg::RemoveClimber),
+            locale.t(Text::Delete)
+        ],
+        " ",
+        button![
+            C!["btn", "btn-sm"],
+            ev(Ev::Click, |_| Msg::CancelRemoveClimber),
+            locale.t(Text::Cancel)
+        ],
+    ]
+}
+
+/// Chips for picking who new ticks are logged for. Hidden until there are two climbers.
+pub fn view_climber_switcher(settings: &Settings) -> Node<Msg> {
+    if settings.climbers.len() < 2 {
+        return empty![];
+    }
+
+    let locale = settings.locale();
+
+    div![
+        C!["style-chooser", "climber-switcher"],
+        span![C!["chip-label"], locale.t(Text::LoggingFor)],
+        settings
+            .climbers
+            .iter()
+            .map(|(key, climber)| {
+                let key = key.clone();
+
+                span![
+                    C![
+                        "chip",
+                        IF!(settings.active_climber.as_ref() == Some(&key) => "active")
+                    ],
+                    ev(Ev::Click, move |_| Msg::ChooseClimber(key)),
+                    climber.name.as_str()
+                ]
+            })
+            .collect::<Vec<Node<Msg>>>()
+    ]
+}
+
+/// The climbers who have sent a route.
+pub fn view_senders(route: &Route, settings: &Settings) -> Node<Msg> {
+    if settings.climbers.len() < 2 {
+        return empty![];
+    }
+
+    let senders = settings
+        .climbers
+        .iter()
+        .filter(|(key, _climber)| {
+            route.ticks.iter().any(|tick| {
+                matches!(tick.typ, TickType::Ascent) && settings.tick_climber(tick) == Some(*key)
+            })
+        })
+        .map(|(_key, climber)| {
+            span![
+                C!["label", "label-rounded", "climber-sent"],
+                climber.name.as_str()
+            ]
+        })
+        .collect::<Vec<Node<Msg>>>();
+
+    span![C!["climber-senders"], senders]
+}
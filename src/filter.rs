@@ -0,0 +1,155 @@
+use chrono::Duration;
+use seed::prelude::*;
+
+use crate::{Route, TickType};
+
+pub const UNTOUCHED_DAYS: [i64; 3] = [7, 14, 30];
+
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Filter {
+    pub query: String,
+    pub sections: Vec<String>,
+    pub grades: Vec<String>,
+    pub colors: Vec<String>,
+    pub sent: Option<bool>,
+    pub project: bool,
+    pub untouched_days: Option<i64>,
+}
+
+#[derive(Clone, Debug)]
+pub enum FilterToggle {
+    Section(String),
+    Grade(String),
+    Color(String),
+    Sent(bool),
+    Project,
+    UntouchedDays(i64),
+}
+
+impl Filter {
+    pub fn from_url_search(search: &UrlSearch) -> Self {
+        let values = |key: &str| search.get(key).cloned().unwrap_or_default();
+        let first = |key: &str| search.get(key).and_then(|values| values.first().cloned());
+
+        Self {
+            query: first("q").unwrap_or_default(),
+            sections: values("section"),
+            grades: values("grade"),
+            colors: values("color"),
+            sent: first("sent").map(|sent| sent == "1"),
+            project: first("project").is_some(),
+            untouched_days: first("untouched").and_then(|days| days.parse().ok()),
+        }
+    }
+
+    pub fn to_url_search(&self) -> UrlSearch {
+        let mut search = UrlSearch::default();
+
+        if !self.query.is_empty() {
+            search.push_value("q", self.query.clone());
+        }
+        for section in &self.sections {
+            search.push_value("section", section.clone());
+        }
+        for grade in &self.grades {
+            search.push_value("grade", grade.clone());
+        }
+        for color in &self.colors {
+            search.push_value("color", color.clone());
+        }
+        if let Some(sent) = self.sent {
+            search.push_value("sent", if sent { "1" } else { "0" }.to_string());
+        }
+        if self.project {
+            search.push_value("project", "1".to_string());
+        }
+        if let Some(days) = self.untouched_days {
+            search.push_value("untouched", days.to_string());
+        }
+
+        search
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn toggle(&mut self, toggle: FilterToggle) {
+        match toggle {
+            FilterToggle::Section(section) => toggle_value(&mut self.sections, section),
+            FilterToggle::Grade(grade) => toggle_value(&mut self.grades, grade),
+            FilterToggle::Color(color) => toggle_value(&mut self.colors, color),
+            FilterToggle::Sent(sent) => {
+                self.sent = if self.sent == Some(sent) {
+                    None
+                } else {
+                    Some(sent)
+                }
+            }
+            FilterToggle::Project => self.project = !self.project,
+            FilterToggle::UntouchedDays(days) => {
+                self.untouched_days = if self.untouched_days == Some(days) {
+                    None
+                } else {
+                    Some(days)
+                }
+            }
+        }
+    }
+
+    pub fn matches(&self, route: &Route, now: i64) -> bool {
+        if !self.sections.is_empty() && !self.sections.contains(&route.section) {
+            return false;
+        }
+        if !self.grades.is_empty() && !self.grades.contains(&route.grade) {
+            return false;
+        }
+        if !self.colors.is_empty() && !self.colors.contains(&route.color) {
+            return false;
+        }
+        if self.project && !route.project {
+            return false;
+        }
+
+        if let Some(sent) = self.sent {
+            let is_sent = route
+                .ticks
+                .iter()
+                .any(|tick| matches!(tick.typ, TickType::Ascent));
+
+            if is_sent != sent {
+                return false;
+            }
+        }
+
+        if let Some(days) = self.untouched_days {
+            let cutoff = now - Duration::days(days).num_seconds();
+
+            if route.ticks.iter().any(|tick| tick.timestamp > cutoff) {
+                return false;
+            }
+        }
+
+        self.query.is_empty() || route_matches_query(route, &self.query)
+    }
+}
+
+/// Case-insensitive search over a route's description, beta and tick notes.
+fn route_matches_query(route: &Route, query: &str) -> bool {
+    let query = query.to_lowercase();
+
+    route.title.to_lowercase().contains(&query)
+        || route.beta.to_lowercase().contains(&query)
+        || route
+            .ticks
+            .iter()
+            .any(|tick| tick.note.to_lowercase().contains(&query))
+}
+
+fn toggle_value(values: &mut Vec<String>, value: String) {
+    if let Some(pos) = values.iter().position(|v| *v == value) {
+        values.remove(pos);
+    } else {
+        values.push(value);
+    }
+}
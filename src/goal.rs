@@ -0,0 +1,393 @@
+//! Goals such as "send 3 V5s this month", "20 sessions this quarter" or "flash a 11-".
+//! A goal with a grade counts that grade and anything harder in the same grade system.
+//! Goals for a period start over with each new period.
+
+use chrono::{Datelike, NaiveDate, Utc};
+use indexmap::IndexMap;
+use itertools::Itertools;
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{Locale, Text};
+use crate::{Msg, PersistedData, Route, RouteId, Settings, Tick, TickType};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum GoalKind {
+    Sends,
+    Flashes,
+    Sessions,
+}
+
+impl GoalKind {
+    const ALL: [GoalKind; 3] = [GoalKind::Sends, GoalKind::Flashes, GoalKind::Sessions];
+
+    fn label(&self) -> Text {
+        match self {
+            GoalKind::Sends => Text::Sends,
+            GoalKind::Flashes => Text::Flashes,
+            GoalKind::Sessions => Text::Sessions,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Period {
+    Week,
+    Month,
+    Quarter,
+    Year,
+    /// From the day the goal was set, for one-off goals.
+    Ever,
+}
+
+impl Period {
+    const ALL: [Period; 5] = [
+        Period::Week,
+        Period::Month,
+        Period::Quarter,
+        Period::Year,
+        Period::Ever,
+    ];
+
+    fn label(&self) -> Text {
+        match self {
+            Period::Week => Text::PeriodWeek,
+            Period::Month => Text::PeriodMonth,
+            Period::Quarter => Text::PeriodQuarter,
+            Period::Year => Text::PeriodYear,
+            Period::Ever => Text::PeriodEver,
+        }
+    }
+
+    /// The name in the goal form, where "ever" has no phrase of its own.
+    fn choice(&self) -> Text {
+        match self {
+            Period::Ever => Text::Once,
+            _ => self.label(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Goal {
+    pub kind: GoalKind,
+    /// The easiest grade that counts. Not used for sessions.
+    pub grade: Option<String>,
+    pub count: u32,
+    pub period: Period,
+    pub created_at: i64,
+    /// When the goal was last reached, so each period is only celebrated once.
+    #[serde(default)]
+    pub completed_at: Option<i64>,
+}
+
+impl Goal {
+    pub fn label(&self, settings: &Settings) -> String {
+        let locale = settings.locale();
+        let period = locale.t(self.period.label());
+        let grade = self
+            .grade
+            .as_ref()
+            .map(|grade| settings.grades.get(grade).map_or(grade, |g| &g.label));
+
+        let text = match (self.kind, grade) {
+            (GoalKind::Sends, None) => locale.fill(Text::GoalSends, &[&self.count, &period]),
+            (GoalKind::Sends, Some(grade)) => {
+                locale.fill(Text::GoalSendsGrade, &[&self.count, &period, grade])
+            }
+            (GoalKind::Flashes, None) => locale.fill(Text::GoalFlashes, &[&self.count, &period]),
+            (GoalKind::Flashes, Some(grade)) => {
+                locale.fill(Text::GoalFlashesGrade, &[&self.count, &period, grade])
+            }
+            (GoalKind::Sessions, _) => locale.fill(Text::GoalSessions, &[&self.count, &period]),
+        };
+
+        text.trim().to_string()
+    }
+
+    /// The first climbing day that counts towards the goal.
+    fn period_start(&self, settings: &Settings) -> NaiveDate {
+        let today = settings.today();
+
+        match self.period {
+            Period::Week => settings.week_start(today),
+            Period::Month => today.with_day(1).unwrap(),
+            // The first of a month always exists, so `today` is never the fallback.
+            Period::Quarter => {
+                NaiveDate::from_ymd_opt(today.year(), (today.month0() / 3) * 3 + 1, 1)
+                    .unwrap_or(today)
+            }
+            Period::Year => NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap_or(today),
+            Period::Ever => settings.climbing_day(self.created_at),
+        }
+    }
+
+    /// Progress towards the goal, capped at `count`.
+    pub fn progress(&self, routes: &IndexMap<RouteId, Route>, settings: &Settings) -> u32 {
+        let start = self.period_start(settings);
+        let ticks = routes
+            .values()
+            .flat_map(|route| route.ticks.iter().map(move |tick| (route, tick)))
+            .filter(|(_route, tick)| is_mine(tick, settings))
+            .filter(|(_route, tick)| settings.climbing_day(tick.timestamp) >= start);
+
+        let done = match self.kind {
+            GoalKind::Sessions => ticks
+                .map(|(_route, tick)| settings.climbing_day(tick.timestamp))
+                .unique()
+                .count(),
+            GoalKind::Sends => ticks
+                .filter(|(route, tick)| {
+                    matches!(tick.typ, TickType::Ascent)
+                        && self.grade_counts(&route.grade, settings)
+                })
+                .count(),
+            GoalKind::Flashes => ticks
+                .filter(|(route, tick)| {
+                    tick.is_flash() && self.grade_counts(&route.grade, settings)
+                })
+                .count(),
+        };
+
+        (done as u32).min(self.count)
+    }
+
+    fn grade_counts(&self, grade: &str, settings: &Settings) -> bool {
+        let min = match &self.grade {
+            Some(min) => min,
+            None => return true,
+        };
+
+        match (settings.grades.get(grade), settings.grades.get(min)) {
+            (Some(grade), Some(min)) => grade.group == min.group && grade.sort >= min.sort,
+            _ => false,
+        }
+    }
+
+    fn is_complete(&self, routes: &IndexMap<RouteId, Route>, settings: &Settings) -> bool {
+        self.progress(routes, settings) >= self.count
+    }
+
+    /// Whether the goal has already been reached in the current period.
+    fn completed_this_period(&self, settings: &Settings) -> bool {
+        self.completed_at.is_some_and(|completed_at| {
+            settings.climbing_day(completed_at) >= self.period_start(settings)
+        })
+    }
+}
+
+/// Ticks count for the climber logging on this device.
+fn is_mine(tick: &Tick, settings: &Settings) -> bool {
+    settings.active_climber.is_none()
+        || settings.tick_climber(tick) == settings.active_climber.as_ref()
+}
+
+/// Marks goals that have just been reached, returning what to congratulate.
+pub fn check(persisted: &mut PersistedData) -> Vec<String> {
+    let routes = &persisted.routes;
+    let settings = &persisted.settings;
+    let now = Utc::now().timestamp();
+
+    persisted
+        .goals
+        .iter_mut()
+        .filter(|goal| !goal.completed_this_period(settings) && goal.is_complete(routes, settings))
+        .map(|goal| {
+            goal.completed_at = Some(now);
+            goal.label(settings)
+        })
+        .collect()
+}
+
+/// The new goal form.
+pub struct GoalDraft {
+    pub kind: GoalKind,
+    /// Empty for any grade.
+    pub grade: String,
+    pub count: String,
+    pub period: Period,
+}
+
+impl Default for GoalDraft {
+    fn default() -> Self {
+        GoalDraft {
+            kind: GoalKind::Sends,
+            grade: String::new(),
+            count: "3".to_string(),
+            period: Period::Month,
+        }
+    }
+}
+
+impl GoalDraft {
+    pub fn to_goal(&self) -> Option<Goal> {
+        let count = self.count.trim().parse().ok().filter(|count| *count > 0)?;
+        let grade = Some(self.grade.clone())
+            .filter(|grade| !grade.is_empty() && self.kind != GoalKind::Sessions);
+
+        Some(Goal {
+            kind: self.kind,
+            grade,
+            count,
+            period: self.period,
+            created_at: Utc::now().timestamp(),
+            completed_at: None,
+        })
+    }
+}
+
+pub fn view_goals(
+    goals: &[Goal],
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+    draft: &GoalDraft,
+) -> Node<Msg> {
+    let locale = settings.locale();
+
+    div![
+        C!["main card", "goals"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Goals)]
+        ],
+        div![
+            C!["card-body"],
+            goals
+                .iter()
+                .enumerate()
+                .map(|(index, goal)| {
+                    let done = goal.progress(routes, settings);
+
+                    div![
+                        C!["goal", IF!(done >= goal.count => "done")],
+                        div![
+                            goal.label(settings),
+                            button![
+                                C!["btn", "btn-clear", "float-right"],
+                                attrs! {
+                                    At::AriaLabel => locale.t(Text::Delete);
+                                },
+                                ev(Ev::Click, move |_| Msg::RemoveGoal(index)),
+                            ],
+                        ],
+                        div![
+                            C!["goal-progress"],
+                            progress![
+                                C!["progress"],
+                                attrs! {
+                                    At::Value => done;
+                                    At::Max => goal.count;
+                                }
+                            ],
+                            small![format!("{}/{}", done, goal.count)],
+                        ],
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>(),
+            view_goal_form(draft, settings, locale),
+        ]
+    ]
+}
+
+fn view_goal_form(draft: &GoalDraft, settings: &Settings, locale: Locale) -> Node<Msg> {
+    div![
+        C!["input-group", "goal-form"],
+        input![
+            C!["form-input"],
+            attrs! {
+                At::Type => "number";
+                At::Min => 1;
+                At::Value => draft.count;
+            },
+            input_ev(Ev::Input, Msg::GoalCountChanged),
+        ],
+        select![
+            C!["form-select"],
+            GoalKind::ALL
+                .iter()
+                .enumerate()
+                .map(|(index, kind)| option![
+                    attrs! {
+                        At::Value => index;
+                        At::Selected => (draft.kind == *kind).as_at_value();
+                    },
+                    locale.t(kind.label())
+                ])
+                .collect::<Vec<Node<Msg>>>(),
+            input_ev(Ev::Change, |value| {
+                value
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| GoalKind::ALL.get(index).copied())
+                    .map(Msg::ChooseGoalKind)
+            }),
+        ],
+        IF!(draft.kind != GoalKind::Sessions => select![
+            C!["form-select"],
+            option![
+                attrs! {
+                    At::Value => "";
+                    At::Selected => draft.grade.is_empty().as_at_value();
+                },
+                locale.t(Text::AnyGrade)
+            ],
+            settings
+                .grades
+                .iter()
+                .map(|(key, grade)| option![
+                    attrs! {
+                        At::Value => key;
+                        At::Selected => (draft.grade == *key).as_at_value();
+                    },
+                    locale.fill(Text::GradeOrHarder, &[&grade.label])
+                ])
+                .collect::<Vec<Node<Msg>>>(),
+            input_ev(Ev::Change, Msg::GoalGradeChanged),
+        ]),
+        select![
+            C!["form-select"],
+            Period::ALL
+                .iter()
+                .enumerate()
+                .map(|(index, period)| option![
+                    attrs! {
+                        At::Value => index;
+                        At::Selected => (draft.period == *period).as_at_value();
+                    },
+                    locale.t(period.choice())
+                ])
+                .collect::<Vec<Node<Msg>>>(),
+            input_ev(Ev::Change, |value| {
+                value
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| Period::ALL.get(index).copied())
+                    .map(Msg::ChooseGoalPeriod)
+            }),
+        ],
+        button![
+            C!["btn", "btn-primary", "input-group-btn"],
+            ev(Ev::Click, |_| Msg::AddGoal),
+            locale.t(Text::AddGoal)
+        ],
+    ]
+}
+
+/// Congratulations for goals reached since the last dismissal.
+pub fn view_completed(completed: &[String], locale: Locale) -> Node<Msg> {
+    if completed.is_empty() {
+        return empty![];
+    }
+
+    div![
+        C!["toast", "toast-success", "goal-toast"],
+        button![
+            C!["btn", "btn-clear", "float-right"],
+            ev(Ev::Click, |_| Msg::DismissCompletedGoals)
+        ],
+        completed
+            .iter()
+            .map(|goal| div![locale.fill(Text::GoalReached, &[goal])])
+            .collect::<Vec<Node<Msg>>>(),
+    ]
+}
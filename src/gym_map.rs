@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+use seed::{prelude::*, *};
+
+use crate::i18n::{Locale, Text};
+use crate::section::Section;
+use crate::{Msg, Route, RouteId, Settings, TickType};
+
+/// Section polygons are drawn in this coordinate space.
+const WIDTH: i32 = 200;
+const HEIGHT: i32 = 100;
+
+#[derive(Default, Clone, Copy)]
+pub struct WallActivity {
+    pub sends: u32,
+    pub projects: u32,
+}
+
+/// Whether the gym has a map. Sections without an outline are still picked from the grid.
+pub fn has_map(sections: &IndexMap<String, Section>) -> bool {
+    sections.values().any(Section::on_map)
+}
+
+/// Sends and projects on the routes currently up, per section.
+pub fn activity_by_wall(routes: &IndexMap<RouteId, Route>) -> HashMap<&str, WallActivity> {
+    let mut walls: HashMap<&str, WallActivity> = HashMap::new();
+
+    for route in routes.values().filter(|route| !route.retired) {
+        let wall = walls.entry(route.section.as_str()).or_default();
+
+        wall.sends += route
+            .ticks
+            .iter()
+            .filter(|tick| matches!(tick.typ, TickType::Ascent))
+            .count() as u32;
+        if route.project {
+            wall.projects += 1;
+        }
+    }
+
+    walls
+}
+
+fn level(sends: u32, max: u32) -> u32 {
+    if sends == 0 || max == 0 {
+        0
+    } else {
+        1 + 3 * sends / max
+    }
+}
+
+fn points(section: &Section) -> String {
+    section
+        .polygon
+        .iter()
+        .map(|(x, y)| format!("{},{}", x, y))
+        .join(" ")
+}
+
+fn view_map(
+    sections: &IndexMap<String, Section>,
+    wall: impl Fn(&String) -> (String, Msg),
+) -> Node<Msg> {
+    svg![
+        attrs! {
+            At::ViewBox => format!("0 0 {} {}", WIDTH, HEIGHT);
+        },
+        sections
+            .iter()
+            .filter(|(_k, section)| section.on_map())
+            .map(|(key, section)| {
+                let (classes, msg) = wall(key);
+                let points = points(section);
+
+                let count = section.polygon.len() as i32;
+                let cx = section.polygon.iter().map(|(x, _y)| x).sum::<i32>() / count;
+                let cy = section.polygon.iter().map(|(_x, y)| y).sum::<i32>() / count;
+
+                g![
+                    C![classes.as_str()],
+                    ev(Ev::Click, move |_| msg),
+                    polygon![attrs! {
+                        At::Points => points;
+                    }],
+                    text![
+                        attrs! {
+                            At::X => cx;
+                            At::Y => cy;
+                            At::TextAnchor => "middle";
+                            At::DominantBaseline => "middle";
+                        },
+                        section.label.as_str()
+                    ],
+                ]
+            })
+            .collect::<Vec<Node<Msg>>>()
+    ]
+}
+
+/// The gym map as a section picker, for the new route modal and the quick log.
+pub fn view_section_picker(
+    sections: &IndexMap<String, Section>,
+    chosen: &str,
+    choose: impl Fn(String) -> Msg,
+) -> Node<Msg> {
+    div![
+        C!["gym-map", "gym-map-picker"],
+        view_map(sections, |key| {
+            (
+                if key == chosen { "active" } else { "" }.to_string(),
+                choose(key.clone()),
+            )
+        })
+    ]
+}
+
+/// The gym map shaded by sends on each wall's current routes. Walls with projects are
+/// outlined, and clicking a wall lists its routes.
+pub fn view_wall_heatmap(routes: &IndexMap<RouteId, Route>, settings: &Settings) -> Node<Msg> {
+    if !has_map(&settings.sections) {
+        return empty![];
+    }
+
+    let locale = settings.locale();
+    let walls = activity_by_wall(routes);
+    let max = walls.values().map(|wall| wall.sends).max().unwrap_or(0);
+
+    div![
+        C!["main card", "gym-map"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Walls)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.t(Text::WallsCaption)
+            ],
+        ],
+        div![
+            C!["card-body"],
+            view_map(&settings.sections, |key| {
+                let wall = walls.get(key.as_str()).copied().unwrap_or_default();
+
+                (
+                    format!(
+                        "heat-{} {}",
+                        level(wall.sends, max),
+                        if wall.projects > 0 { "has-project" } else { "" }
+                    ),
+                    Msg::OpenSection(key.clone()),
+                )
+            })
+        ]
+    ]
+}
+
+/// Drawing the gym map: pick a wall, then tap its corners on the map one after another.
+pub fn view_map_editor(
+    sections: &IndexMap<String, Section>,
+    drawing: Option<&str>,
+    locale: Locale,
+) -> Node<Msg> {
+    div![
+        C!["main card", "gym-map", "gym-map-editor"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::GymMap)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.t(Text::GymMapCaption)
+            ],
+        ],
+        div![
+            C!["card-body"],
+            div![
+                C!["style-chooser"],
+                sections
+                    .iter()
+                    .map(|(key, section)| {
+                        let choice = if drawing == Some(key.as_str()) {
+                            None
+                        } else {
+                            Some(key.clone())
+                        };
+
+                        span![
+                            C![
+                                "chip",
+                                IF!(choice.is_none() => "active"),
+                                IF!(section.on_map() => "on-map"),
+                            ],
+                            ev(Ev::Click, move |_| Msg::DrawOutline(choice)),
+                            section.label.as_str()
+                        ]
+                    })
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            svg![
+                attrs! {
+                    At::ViewBox => format!("0 0 {} {}", WIDTH, HEIGHT);
+                },
+                drawing.map(|_| mouse_ev(Ev::Click, |event| {
+                    let target = event
+                        .current_target()?
+                        .dyn_into::<web_sys::Element>()
+                        .ok()?;
+                    let width = f64::from(target.client_width());
+                    let height = f64::from(target.client_height());
+
+                    if width > 0.0 && height > 0.0 {
+                        Some(Msg::AddOutlinePoint(
+                            (f64::from(event.offset_x()) / width * f64::from(WIDTH)).round() as i32,
+                            (f64::from(event.offset_y()) / height * f64::from(HEIGHT)).round()
+                                as i32,
+                        ))
+                    } else {
+                        None
+                    }
+                })),
+                sections
+                    .iter()
+                    .filter(|(_k, section)| !section.polygon.is_empty())
+                    .map(|(key, section)| {
+                        let active = drawing == Some(key.as_str());
+
+                        g![
+                            C![IF!(active => "active")],
+                            polygon![attrs! {
+                                At::Points => points(section);
+                            }],
+                            IF!(active => section
+                                .polygon
+                                .iter()
+                                .map(|(x, y)| {
+                                    circle![attrs! {
+                                        At::Cx => x;
+                                        At::Cy => y;
+                                        At::R => 1.5;
+                                    }]
+                                })
+                                .collect::<Vec<Node<Msg>>>()),
+                        ]
+                    })
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            drawing.map(|key| {
+                let key = key.to_string();
+
+                div![
+                    C!["gym-map-editor-actions"],
+                    button![
+                        C!["btn", "btn-sm"],
+                        ev(Ev::Click, move |_| Msg::ClearOutline(key)),
+                        locale.t(Text::ClearOutline)
+                    ],
+                    button![
+                        C!["btn", "btn-sm", "btn-primary"],
+                        ev(Ev::Click, |_| Msg::DrawOutline(None)),
+                        locale.t(Text::DoneDrawing)
+                    ],
+                ]
+            }),
+        ]
+    ]
+}
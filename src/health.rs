@@ -0,0 +1,188 @@
+//! How well the app is set up to keep working and keep our ticks: whether the service worker
+//! registered, whether the browser may evict our storage, how much of LocalStorage we use and
+//! how long ago the data last left the device in an export.
+
+use chrono::{TimeZone, Utc};
+use seed::{prelude::*, *};
+
+use crate::i18n::{Locale, Text};
+use crate::{util, Msg};
+
+const LAST_EXPORT_KEY: &str = "gymticks-last-export";
+/// Roughly what browsers let a site keep in LocalStorage.
+const LOCAL_STORAGE_LIMIT: f64 = 5_000_000.0;
+
+#[derive(Clone, Default, Debug)]
+pub enum WorkerStatus {
+    #[default]
+    Registering,
+    Registered,
+    /// No service workers here, e.g. on plain http.
+    Unsupported,
+    Failed(String),
+}
+
+/// What `navigator.storage` reports, with `None` for whatever the browser doesn't say.
+#[derive(Clone, Default, Debug)]
+pub struct StorageStatus {
+    pub persisted: Option<bool>,
+    pub usage: Option<f64>,
+    pub quota: Option<f64>,
+}
+
+impl StorageStatus {
+    /// Reads the `[persisted, usage, quota]` triple from `checkStorage`.
+    pub fn from_js(status: &JsValue) -> Self {
+        let status = js_sys::Array::from(status);
+
+        StorageStatus {
+            persisted: status.get(0).as_bool(),
+            usage: status.get(1).as_f64(),
+            quota: status.get(2).as_f64(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Health {
+    pub worker: WorkerStatus,
+    pub storage: Option<StorageStatus>,
+    /// Bytes our keys take up in LocalStorage.
+    pub local_storage: Option<usize>,
+    pub last_export: Option<i64>,
+}
+
+impl Health {
+    pub fn load(worker: WorkerStatus) -> Self {
+        Health {
+            worker,
+            storage: None,
+            local_storage: local_storage_size(),
+            last_export: LocalStorage::get(LAST_EXPORT_KEY).ok(),
+        }
+    }
+
+    pub fn record_export(&mut self) {
+        let now = Utc::now().timestamp();
+        self.last_export = Some(now);
+        let _ = LocalStorage::insert(LAST_EXPORT_KEY, &now);
+    }
+}
+
+/// The serialized size of our keys and their values. Snapshots are counted too, since they
+/// share the quota with the data.
+pub fn local_storage_size() -> Option<usize> {
+    let storage = LocalStorage::storage().ok()?;
+    let len = LocalStorage::len().ok()?;
+
+    let size = (0..len)
+        .filter_map(|index| LocalStorage::key(index).ok())
+        .filter(|key| key.starts_with("gymticks"))
+        .map(|key| {
+            let value = storage.get_item(&key).ok().flatten().unwrap_or_default();
+            key.len() + value.len()
+        })
+        .sum();
+
+    Some(size)
+}
+
+/// Whether this browser can run service workers at all. `navigator.serviceWorker` is missing
+/// outside secure contexts, and using it there would throw.
+pub fn workers_supported() -> bool {
+    js_sys::Reflect::has(&window().navigator(), &JsValue::from_str("serviceWorker"))
+        .unwrap_or(false)
+}
+
+pub fn error_message(error: &JsValue) -> String {
+    error
+        .dyn_ref::<js_sys::Error>()
+        .map(|error| String::from(error.message()))
+        .or_else(|| error.as_string())
+        .unwrap_or_default()
+}
+
+fn megabytes(bytes: f64) -> String {
+    format!("{:.1} MB", bytes / 1_000_000.0)
+}
+
+pub fn view_health(health: &Health, data_url: Url, locale: Locale) -> Node<Msg> {
+    let worker = match &health.worker {
+        WorkerStatus::Registering => locale.t(Text::WorkerRegistering).to_string(),
+        WorkerStatus::Registered => locale.t(Text::WorkerRegistered).to_string(),
+        WorkerStatus::Unsupported => locale.t(Text::WorkerUnsupported).to_string(),
+        WorkerStatus::Failed(error) => locale.fill(Text::WorkerFailed, &[error]),
+    };
+
+    let storage = health.storage.as_ref();
+    let persisted = storage.and_then(|storage| storage.persisted);
+    let usage = storage
+        .and_then(|storage| Some((storage.usage?, storage.quota?)))
+        .map(|(usage, quota)| {
+            locale.fill(Text::StorageUsage, &[&megabytes(usage), &megabytes(quota)])
+        });
+
+    let local_storage = health.local_storage.map(|size| {
+        locale.fill(
+            Text::LocalStorageUsage,
+            &[&megabytes(size as f64), &megabytes(LOCAL_STORAGE_LIMIT)],
+        )
+    });
+
+    let last_export = health
+        .last_export
+        .and_then(|exported_at| Utc.timestamp_opt(exported_at, 0).single())
+        .map(|exported_at| util::time_diff_in_words(exported_at, Utc::now(), locale));
+
+    let row = |label: Text, value: Node<Msg>| tr![td![locale.t(label)], td![value]];
+
+    div![
+        C!["main card", "health"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::About)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.fill(Text::AppVersion, &[&env!("CARGO_PKG_VERSION")])
+            ],
+        ],
+        div![
+            C!["card-body"],
+            table![
+                C!["table"],
+                tbody![
+                    row(Text::Offline, span![worker]),
+                    row(
+                        Text::Storage,
+                        div![
+                            span![locale.t(match persisted {
+                                Some(true) => Text::StoragePersisted,
+                                Some(false) => Text::StorageNotPersisted,
+                                None => Text::Unknown,
+                            })],
+                            usage.map(|usage| div![small![C!["text-gray"], usage]]),
+                            local_storage.map(|size| div![small![C!["text-gray"], size]]),
+                            IF!(persisted == Some(false) => button![
+                                C!["btn", "btn-sm"],
+                                ev(Ev::Click, |_| Msg::RequestPersistentStorage),
+                                locale.t(Text::KeepMyData)
+                            ]),
+                        ]
+                    ),
+                    row(
+                        Text::LastExport,
+                        a![
+                            attrs! {
+                                At::Href => data_url
+                            },
+                            match last_export {
+                                Some(age) => locale.fill(Text::LastExportAge, &[&age]),
+                                None => locale.t(Text::NoExportYet).to_string(),
+                            }
+                        ]
+                    ),
+                ]
+            ]
+        ],
+    ]
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
+use indexmap::IndexMap;
+use seed::{prelude::*, *};
+
+use crate::i18n::Text;
+use crate::{Msg, Route, RouteId, Settings, TickType};
+
+const WEEKS: i64 = 53;
+const CELL: i64 = 12;
+
+#[derive(Default, Clone, Copy)]
+pub struct DayActivity {
+    pub sends: u32,
+    pub ticks: u32,
+}
+
+pub fn activity_by_day(
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+) -> HashMap<NaiveDate, DayActivity> {
+    let mut days: HashMap<NaiveDate, DayActivity> = HashMap::new();
+
+    for tick in routes.values().flat_map(|route| &route.ticks) {
+        let day = days
+            .entry(settings.climbing_day(tick.timestamp))
+            .or_default();
+
+        day.ticks += 1;
+        if let TickType::Ascent = tick.typ {
+            day.sends += 1;
+        }
+    }
+
+    days
+}
+
+fn level(ticks: u32) -> u32 {
+    match ticks {
+        0 => 0,
+        1..=2 => 1,
+        3..=5 => 2,
+        6..=9 => 3,
+        _ => 4,
+    }
+}
+
+/// A year of activity, one column per week and one row per weekday, in the style of a
+/// GitHub contribution graph. Clicking a day opens that day's session.
+pub fn view_heatmap(routes: &IndexMap<RouteId, Route>, settings: &Settings) -> Node<Msg> {
+    let activity = activity_by_day(routes, settings);
+
+    let locale = settings.locale();
+    let today = settings.today();
+    let start = settings.week_start(today - Duration::weeks(WEEKS - 1));
+
+    let days_climbed = activity
+        .iter()
+        .filter(|(date, day)| **date >= start && day.ticks > 0)
+        .count();
+
+    let cells = (0..)
+        .map(|offset| (offset, start + Duration::days(offset)))
+        .take_while(|(_offset, date)| *date <= today)
+        .map(|(offset, date)| {
+            let ticks = activity.get(&date).map_or(0, |day| day.ticks);
+
+            rect![
+                C![format!("heat-{}", level(ticks))],
+                attrs! {
+                    At::X => offset / 7 * CELL;
+                    At::Y => offset % 7 * CELL;
+                    At::Width => CELL - 2;
+                    At::Height => CELL - 2;
+                    At::Rx => 2;
+                },
+                ev(Ev::Click, move |_| Msg::OpenDay(date)),
+            ]
+        })
+        .collect::<Vec<Node<Msg>>>();
+
+    div![
+        C!["main card", "heatmap"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Activity)]
+        ],
+        div![
+            C!["card-body"],
+            svg![
+                attrs! {
+                    At::ViewBox => format!("0 0 {} {}", WEEKS * CELL, 7 * CELL);
+                },
+                cells
+            ],
+            small![locale.fill(Text::DaysClimbed, &[&days_climbed])],
+        ]
+    ]
+}
@@ -0,0 +1,903 @@
+use std::fmt::Display;
+
+use chrono::Weekday;
+use seed::window;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::De];
+
+    /// The locale's name, in that locale.
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+        }
+    }
+
+    /// Picks a locale from the browser's language, falling back to English.
+    pub fn detect() -> Self {
+        let language = window().navigator().language().unwrap_or_default();
+
+        if language.starts_with("de") {
+            Locale::De
+        } else {
+            Locale::En
+        }
+    }
+
+    pub fn t(self, text: Text) -> &'static str {
+        match self {
+            Locale::En => en(text),
+            Locale::De => de(text),
+        }
+    }
+
+    /// Translates `text` and replaces `{0}`, `{1}`, ... with `args`.
+    pub fn fill(self, text: Text, args: &[&dyn Display]) -> String {
+        args.iter()
+            .enumerate()
+            .fold(self.t(text).to_string(), |message, (i, arg)| {
+                message.replace(&format!("{{{}}}", i), &arg.to_string())
+            })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Text {
+    // Navigation
+    Routes,
+    Stats,
+    Retired,
+    Data,
+    Settings,
+    NothingHere,
+
+    // Route editing
+    DescriptionOfRoute,
+    NoteForThisTick,
+    Beta,
+    Note,
+    SetOn,
+    ExpectedStrip,
+    SaveChanges,
+    RetireRoute,
+    AddWithoutTick,
+    DuplicateRoute,
+    UseExistingRoute,
+    CreateAnyway,
+    Edit,
+    Unretire,
+    AddPhoto,
+    ReplacePhoto,
+    RemovePhoto,
+    Project,
+    Soft,
+    Hard,
+
+    // Ticks
+    Send,
+    Attempt,
+
+    // Route list
+    StrippingSoon,
+    StripInDays,
+    Sent,
+    Unsent,
+    Unattempted,
+    SearchRoutes,
+    UntouchedDays,
+    NoRoutesMatch,
+    NoRetiredRoutes,
+    RetiredLabel,
+    SendsFlash,
+    SendsAfterAttempts,
+    AttemptsLastAttempt,
+    AttemptsLastSend,
+    SetAgo,
+
+    // Sorting and grouping
+    Sort,
+    Group,
+    SortGym,
+    SortGrade,
+    SortActivity,
+    SortAttempts,
+    SortDateSet,
+    GroupSection,
+    GroupGrade,
+    GroupColor,
+    GroupNone,
+
+    // Settings
+    DayCutover,
+    WeekStarts,
+    Language,
+    Weekday(Weekday),
+    Climbers,
+    ClimberName,
+    AddClimber,
+    ClimbersHint,
+    RemoveClimberPrompt,
+    ClimberTicksGoTo,
+    ClimberTicksUnassigned,
+    Cancel,
+    LoggingFor,
+
+    // Data
+    ExportData,
+    ImportData,
+    ExportArchive,
+    ArchiveNotAnArchive,
+    ArchiveCompressed,
+    ArchiveBadManifest,
+    ArchiveNewerVersion,
+    ArchiveMissingFile,
+    ArchiveDamaged,
+    ArchiveBadData,
+    Passphrase,
+    PassphrasePlaceholder,
+    PassphraseConfirmation,
+    PassphraseMismatch,
+    DecryptAndImport,
+    EncryptionPassphraseRequired,
+    EncryptionWrongPassphrase,
+    EncryptionUnsupportedVersion,
+    EncryptionDamaged,
+    EncryptionFailed,
+    Backups,
+    BackupsCaption,
+    NoBackups,
+    BackupDaily,
+    BackupBeforeImport,
+    BackupBeforeRestore,
+    BackupCounts,
+    BackupUnreadable,
+    SaveFailed,
+    SnapshotsDropped,
+    Restore,
+    Delete,
+    Duplicates,
+    DuplicatesCaption,
+    NoDuplicates,
+    RouteTicks,
+    Merge,
+    MergeOrSplit,
+    MergeWith,
+    ChooseRoute,
+    KeepTheseDetails,
+    UseOtherDetails,
+    SplitCaption,
+    SplitOff,
+    MergedRoutes,
+    SplitRoute,
+    Undo,
+
+    // Stats
+    Activity,
+    DaysClimbed,
+    DayTitleFormat,
+    DaySummary,
+    NoTicksOnDay,
+    SendsToday,
+    SendsThisWeek,
+    SendsTotal,
+    Style,
+    Today,
+    ThisWeek,
+    Total,
+    Climber,
+    Progress,
+    LastWeeks,
+    HardestSend,
+    AverageGrade,
+    Walls,
+    WallsCaption,
+    GymMap,
+    GymMapCaption,
+    ClearOutline,
+    DoneDrawing,
+
+    // Team
+    Team,
+    TeamCaption,
+    OpenTeam,
+    You,
+    Leaderboard,
+    ThisMonth,
+    Points,
+    Sends,
+    Flashes,
+    Challenges,
+    ChallengeSectionSweep,
+    ChallengeMonthlySends,
+    ChallengeGradeSends,
+    ChallengeKindSection,
+    ChallengeKindMonthly,
+    ChallengeKindGrade,
+    AddChallenge,
+    Teammates,
+    TeammatesCaption,
+    TeammateImported,
+    AddTeammates,
+    TeamStorageFull,
+    TeammatesPassphrasePlaceholder,
+
+    // Goals
+    Goals,
+    Sessions,
+    GoalSends,
+    GoalSendsGrade,
+    GoalFlashes,
+    GoalFlashesGrade,
+    GoalSessions,
+    PeriodWeek,
+    PeriodMonth,
+    PeriodQuarter,
+    PeriodYear,
+    PeriodEver,
+    Once,
+    AnyGrade,
+    GradeOrHarder,
+    AddGoal,
+    GoalReached,
+
+    // Achievements
+    Trophies,
+    TrophiesCaption,
+    TrophiesCount,
+    AllTrophies,
+    NextUp,
+    NoTrophies,
+    AchievementUnlocked,
+    AchievementFirstSend,
+    AchievementFirstSendDescription,
+    AchievementSends,
+    AchievementSendsDescription,
+    AchievementStreak,
+    AchievementStreakDescription,
+    AchievementSectionColors,
+    AchievementSectionColorsDescription,
+
+    // Relative time
+    Now,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    Months,
+    Years,
+
+    // Keyboard shortcuts
+    KeyboardShortcuts,
+    ShortcutNewRoute,
+    ShortcutSearch,
+    ShortcutNext,
+    ShortcutPrevious,
+    ShortcutSend,
+    ShortcutAttempt,
+    ShortcutEdit,
+    ShortcutClose,
+    ShortcutHelp,
+
+    // Quick log
+    QuickLog,
+    QuickLogPrompt,
+    QuickLogOn,
+    QuickLogNewRoute,
+    QuickLogged,
+
+    // Footer and install prompt
+    CreatedBy,
+    With,
+    PwaTapHere,
+    PwaAddToHomeScreen,
+    PwaIphoneReason,
+    PwaReason,
+    UpdateAvailable,
+    Reload,
+
+    // About and health
+    About,
+    AppVersion,
+    Offline,
+    WorkerRegistering,
+    WorkerRegistered,
+    WorkerUnsupported,
+    WorkerFailed,
+    Storage,
+    StoragePersisted,
+    StorageNotPersisted,
+    StorageUsage,
+    LocalStorageUsage,
+    KeepMyData,
+    Unknown,
+    LastExport,
+    LastExportAge,
+    NoExportYet,
+}
+
+fn en(text: Text) -> &'static str {
+    match text {
+        Text::Routes => "Routes",
+        Text::Stats => "Stats",
+        Text::Retired => "Retired",
+        Text::Data => "Data",
+        Text::Settings => "Settings",
+        Text::NothingHere => "Nothing here.",
+
+        Text::DescriptionOfRoute => "Description of route",
+        Text::NoteForThisTick => "Note for this tick",
+        Text::Beta => "Beta",
+        Text::Note => "Note",
+        Text::SetOn => "Set on",
+        Text::ExpectedStrip => "Expected strip",
+        Text::SaveChanges => "Save Changes",
+        Text::RetireRoute => "Retire Route",
+        Text::AddWithoutTick => "Add Without Tick",
+        Text::DuplicateRoute => "\"{0}\" is already up with this section, color and grade.",
+        Text::UseExistingRoute => "Use That Route",
+        Text::CreateAnyway => "Add Anyway",
+        Text::Edit => "Edit",
+        Text::Unretire => "Unretire",
+        Text::AddPhoto => "Add Photo",
+        Text::ReplacePhoto => "Replace Photo",
+        Text::RemovePhoto => "Remove Photo",
+        Text::Project => "project",
+        Text::Soft => "soft",
+        Text::Hard => "hard",
+
+        Text::Send => "SND",
+        Text::Attempt => "ATT",
+
+        Text::StrippingSoon => "Stripping Soon",
+        Text::StripInDays => "strip in {0}d",
+        Text::Sent => "sent",
+        Text::Unsent => "unsent",
+        Text::Unattempted => "unattempted",
+        Text::SearchRoutes => "Search routes",
+        Text::UntouchedDays => "untouched {0}d",
+        Text::NoRoutesMatch => "No routes match.",
+        Text::NoRetiredRoutes => "No retired routes.",
+        Text::RetiredLabel => "retired",
+        Text::SendsFlash => "{0} snd (flsh)",
+        Text::SendsAfterAttempts => "{0} snd ({1} att)",
+        Text::AttemptsLastAttempt => "{0} att (att {1})",
+        Text::AttemptsLastSend => "{0} att (snd {1})",
+        Text::SetAgo => "set {0}",
+
+        Text::Sort => "sort",
+        Text::Group => "group",
+        Text::SortGym => "gym",
+        Text::SortGrade => "grade",
+        Text::SortActivity => "activity",
+        Text::SortAttempts => "attempts",
+        Text::SortDateSet => "date set",
+        Text::GroupSection => "section",
+        Text::GroupGrade => "grade",
+        Text::GroupColor => "color",
+        Text::GroupNone => "none",
+
+        Text::DayCutover => "New climbing day starts at",
+        Text::WeekStarts => "week starts",
+        Text::Language => "Language",
+        Text::Climbers => "Climbers",
+        Text::ClimberName => "Name",
+        Text::AddClimber => "Add",
+        Text::ClimbersHint => {
+            "Add everyone who logs on this device to record who climbed each tick. Ticks from before count for the first climber."
+        }
+        Text::RemoveClimberPrompt => "Remove {0}?",
+        Text::ClimberTicksGoTo => "Their {0} ticks go to",
+        Text::ClimberTicksUnassigned => {
+            "No other climbers are left, so their {0} ticks won't have a climber."
+        }
+        Text::Cancel => "Cancel",
+        Text::LoggingFor => "Logging for",
+        Text::Weekday(weekday) => match weekday {
+            Weekday::Mon => "Mon",
+            Weekday::Tue => "Tue",
+            Weekday::Wed => "Wed",
+            Weekday::Thu => "Thu",
+            Weekday::Fri => "Fri",
+            Weekday::Sat => "Sat",
+            Weekday::Sun => "Sun",
+        },
+
+        Text::ExportData => "Export Data",
+        Text::ImportData => "Import Data",
+        Text::ExportArchive => "Export Archive",
+        Text::ArchiveNotAnArchive => "That file isn't a gymticks export.",
+        Text::ArchiveCompressed => "{0} is compressed. Import the archive as it was exported.",
+        Text::ArchiveBadManifest => "The archive's manifest is missing or unreadable.",
+        Text::ArchiveNewerVersion => {
+            "The archive was made by a newer version of gymticks (format {0})."
+        }
+        Text::ArchiveMissingFile => "The archive is missing {0}.",
+        Text::ArchiveDamaged => "{0} in the archive is damaged.",
+        Text::ArchiveBadData => "The data in that file couldn't be read.",
+        Text::Passphrase => "Passphrase",
+        Text::PassphrasePlaceholder => "Leave empty to export unencrypted",
+        Text::PassphraseConfirmation => "Repeat the passphrase to export",
+        Text::PassphraseMismatch => "The passphrases don't match.",
+        Text::DecryptAndImport => "Decrypt and Import",
+        Text::EncryptionPassphraseRequired => "This export is encrypted. Enter its passphrase.",
+        Text::EncryptionWrongPassphrase => {
+            "The passphrase is wrong, or the file has been modified."
+        }
+        Text::EncryptionUnsupportedVersion => {
+            "This export uses encryption version {0}. Update the app to import it."
+        }
+        Text::EncryptionDamaged => "The encrypted export is damaged.",
+        Text::EncryptionFailed => "Encrypting the export failed.",
+        Text::Backups => "Backups",
+        Text::BackupsCaption => {
+            "Kept on this device: one a day for the last week, and one before each import or restore."
+        }
+        Text::NoBackups => "No backups yet.",
+        Text::BackupDaily => "Daily backup",
+        Text::BackupBeforeImport => "Before import",
+        Text::BackupBeforeRestore => "Before restore",
+        Text::BackupCounts => "{0} routes, {1} ticks",
+        Text::BackupUnreadable => "That backup couldn't be read.",
+        Text::SaveFailed => {
+            "Storage on this device is full, so your latest changes aren't saved. Export your data to keep them."
+        }
+        Text::SnapshotsDropped => {
+            "Storage on this device is running out, so older backups were deleted to make room. Export your data to keep a copy."
+        }
+        Text::Restore => "Restore",
+        Text::Delete => "Delete",
+        Text::Duplicates => "Duplicates",
+        Text::DuplicatesCaption => {
+            "Routes up with the same section, color and grade. Merging moves every tick onto one of them."
+        }
+        Text::NoDuplicates => "No duplicate routes.",
+        Text::RouteTicks => "{0} ticks",
+        Text::Merge => "Merge",
+        Text::MergeOrSplit => "Merge or Split",
+        Text::MergeWith => "Merge with another route",
+        Text::ChooseRoute => "Choose a route\u{2026}",
+        Text::KeepTheseDetails => "Keep this route's details",
+        Text::UseOtherDetails => "Use the other route's details",
+        Text::SplitCaption => "Move ticks to a new route",
+        Text::SplitOff => "Split Off {0} Ticks",
+        Text::MergedRoutes => "Merged {0} routes.",
+        Text::SplitRoute => "Moved {0} ticks to a new route.",
+        Text::Undo => "Undo",
+
+        Text::Activity => "Activity",
+        Text::DaysClimbed => "{0} days climbed in the last year",
+        Text::DayTitleFormat => "%A, %B %-d, %Y",
+        Text::DaySummary => "{0} snd, {1} att",
+        Text::NoTicksOnDay => "No ticks on this day.",
+        Text::SendsToday => "Sends Today",
+        Text::SendsThisWeek => "Sends This Week",
+        Text::SendsTotal => "Sends Total",
+        Text::Style => "Style",
+        Text::Today => "Today",
+        Text::ThisWeek => "This Week",
+        Text::Total => "Total",
+        Text::Climber => "Climber",
+        Text::Progress => "Progress",
+        Text::LastWeeks => "Last {0} weeks",
+        Text::HardestSend => "hardest send per week",
+        Text::AverageGrade => "{0}-week average send",
+        Text::Walls => "Walls",
+        Text::WallsCaption => "Sends on current routes. Outlined walls have projects.",
+        Text::GymMap => "Gym Map",
+        Text::GymMapCaption => {
+            "Pick a wall, then tap its corners on the map. Walls without an outline are picked from the grid."
+        }
+        Text::ClearOutline => "Clear Outline",
+        Text::DoneDrawing => "Done",
+
+        Text::Team => "Team",
+        Text::TeamCaption => "Leaderboard and challenges with {0} teammates",
+        Text::OpenTeam => "Open",
+        Text::You => "You",
+        Text::Leaderboard => "Leaderboard",
+        Text::ThisMonth => "This month. A send scores its grade's place in the grade list.",
+        Text::Points => "Points",
+        Text::Sends => "Sends",
+        Text::Flashes => "Flashes",
+        Text::Challenges => "Challenges",
+        Text::ChallengeSectionSweep => "Send every route in {0}",
+        Text::ChallengeMonthlySends => "Send {0} routes this month",
+        Text::ChallengeGradeSends => "Send {0} routes graded {1}",
+        Text::ChallengeKindSection => "Every route in a section",
+        Text::ChallengeKindMonthly => "Sends this month",
+        Text::ChallengeKindGrade => "Sends of a grade",
+        Text::AddChallenge => "Add",
+        Text::Teammates => "Teammates",
+        Text::TeammatesCaption => "Exports your teammates shared with you. They stay on this device and aren't part of your export.",
+        Text::TeammateImported => "imported {0} ago",
+        Text::AddTeammates => "Add Teammates' Exports",
+        Text::TeamStorageFull => "There's no room left on this device to keep these exports.",
+        Text::TeammatesPassphrasePlaceholder => "Only needed for encrypted exports",
+
+        Text::Goals => "Goals",
+        Text::Sessions => "Sessions",
+        Text::GoalSends => "Send {0} routes {1}",
+        Text::GoalSendsGrade => "Send {0} routes of {2} or harder {1}",
+        Text::GoalFlashes => "Flash {0} routes {1}",
+        Text::GoalFlashesGrade => "Flash {0} routes of {2} or harder {1}",
+        Text::GoalSessions => "Climb {0} sessions {1}",
+        Text::PeriodWeek => "this week",
+        Text::PeriodMonth => "this month",
+        Text::PeriodQuarter => "this quarter",
+        Text::PeriodYear => "this year",
+        Text::PeriodEver => "",
+        Text::Once => "once",
+        Text::AnyGrade => "any grade",
+        Text::GradeOrHarder => "{0} or harder",
+        Text::AddGoal => "Add Goal",
+        Text::GoalReached => "Goal reached: {0}",
+
+        Text::Trophies => "Trophies",
+        Text::TrophiesCaption => "Milestones from your ticks. Once earned, they're yours to keep.",
+        Text::TrophiesCount => "{0} earned",
+        Text::AllTrophies => "All Trophies",
+        Text::NextUp => "Next up",
+        Text::NoTrophies => "Send a route to earn your first trophy.",
+        Text::AchievementUnlocked => "Trophy earned: {0}",
+        Text::AchievementFirstSend => "First {0}",
+        Text::AchievementFirstSendDescription => "First send of a new grade",
+        Text::AchievementSends => "{0} sends",
+        Text::AchievementSendsDescription => "Sends in total",
+        Text::AchievementStreak => "{0}-session streak",
+        Text::AchievementStreakDescription => "Sessions in a row with a send",
+        Text::AchievementSectionColors => "Every color in {0}",
+        Text::AchievementSectionColorsDescription => "Sent every color up in a section",
+
+        Text::Now => "now",
+        Text::Minutes => "{0}m",
+        Text::Hours => "{0}h",
+        Text::Days => "{0}d",
+        Text::Weeks => "{0}w",
+        Text::Months => "{0}mo",
+        Text::Years => "{0}y",
+
+        Text::KeyboardShortcuts => "Keyboard Shortcuts",
+        Text::ShortcutNewRoute => "New route",
+        Text::ShortcutSearch => "Search routes",
+        Text::ShortcutNext => "Next route",
+        Text::ShortcutPrevious => "Previous route",
+        Text::ShortcutSend => "Log a send",
+        Text::ShortcutAttempt => "Log an attempt",
+        Text::ShortcutEdit => "Edit route",
+        Text::ShortcutClose => "Close",
+        Text::ShortcutHelp => "Show these shortcuts",
+
+        Text::QuickLog => "Log",
+        Text::QuickLogPrompt => "Pick a section, color and grade.",
+        Text::QuickLogOn => "Logging on {0}.",
+        Text::QuickLogNewRoute => "No route up matches. Logging adds one.",
+        Text::QuickLogged => "{0} logged on {1}, {2} today.",
+
+        Text::CreatedBy => "created by ",
+        Text::With => " with ",
+        Text::PwaTapHere => "Tap the here and choose \"Add to Home Screen.\"",
+        Text::PwaAddToHomeScreen => "Add this app to your Home Screen.",
+        Text::PwaIphoneReason => "Your iPhone will be less likely to delete your data that way.",
+        Text::PwaReason => "It's better that way!",
+        Text::UpdateAvailable => "A new version of gymticks is ready.",
+        Text::Reload => "Reload",
+
+        Text::About => "About",
+        Text::AppVersion => "gymticks {0}",
+        Text::Offline => "Offline use",
+        Text::WorkerRegistering => "Setting up…",
+        Text::WorkerRegistered => "Ready",
+        Text::WorkerUnsupported => "Not available in this browser",
+        Text::WorkerFailed => "Couldn't be set up: {0}",
+        Text::Storage => "Storage",
+        Text::StoragePersisted => "Kept even when the device runs low on space",
+        Text::StorageNotPersisted => "The browser may clear it when the device runs low on space",
+        Text::StorageUsage => "{0} of {1} used",
+        Text::LocalStorageUsage => "Ticks and backups take {0} of about {1}",
+        Text::KeepMyData => "Keep My Data",
+        Text::Unknown => "Unknown",
+        Text::LastExport => "Last export",
+        Text::LastExportAge => "{0} ago",
+        Text::NoExportYet => "None yet",
+    }
+}
+
+fn de(text: Text) -> &'static str {
+    match text {
+        Text::Routes => "Routen",
+        Text::Stats => "Statistik",
+        Text::Retired => "Abgebaut",
+        Text::Data => "Daten",
+        Text::Settings => "Einstellungen",
+        Text::NothingHere => "Hier gibt es nichts.",
+
+        Text::DescriptionOfRoute => "Beschreibung der Route",
+        Text::NoteForThisTick => "Notiz zu diesem Versuch",
+        Text::Beta => "Beta",
+        Text::Note => "Notiz",
+        Text::SetOn => "Geschraubt am",
+        Text::ExpectedStrip => "Abbau voraussichtlich",
+        Text::SaveChanges => "Speichern",
+        Text::RetireRoute => "Route abbauen",
+        Text::AddWithoutTick => "Ohne Eintrag hinzufügen",
+        Text::DuplicateRoute => "„{0}“ hängt schon mit diesem Sektor, dieser Farbe und diesem Grad.",
+        Text::UseExistingRoute => "Diese Route nehmen",
+        Text::CreateAnyway => "Trotzdem hinzufügen",
+        Text::Edit => "Bearbeiten",
+        Text::Unretire => "Wiederherstellen",
+        Text::AddPhoto => "Foto hinzufügen",
+        Text::ReplacePhoto => "Foto ersetzen",
+        Text::RemovePhoto => "Foto entfernen",
+        Text::Project => "Projekt",
+        Text::Soft => "weich",
+        Text::Hard => "hart",
+
+        Text::Send => "DGS",
+        Text::Attempt => "VSN",
+
+        Text::StrippingSoon => "Bald abgebaut",
+        Text::StripInDays => "Abbau in {0}T",
+        Text::Sent => "geklettert",
+        Text::Unsent => "offen",
+        Text::Unattempted => "unversucht",
+        Text::SearchRoutes => "Routen suchen",
+        Text::UntouchedDays => "unberührt {0}T",
+        Text::NoRoutesMatch => "Keine passenden Routen.",
+        Text::NoRetiredRoutes => "Keine abgebauten Routen.",
+        Text::RetiredLabel => "abgebaut",
+        Text::SendsFlash => "{0} dgs (flash)",
+        Text::SendsAfterAttempts => "{0} dgs ({1} vsn)",
+        Text::AttemptsLastAttempt => "{0} vsn (vsn {1})",
+        Text::AttemptsLastSend => "{0} vsn (dgs {1})",
+        Text::SetAgo => "geschraubt {0}",
+
+        Text::Sort => "Sortierung",
+        Text::Group => "Gruppierung",
+        Text::SortGym => "Halle",
+        Text::SortGrade => "Grad",
+        Text::SortActivity => "Aktivität",
+        Text::SortAttempts => "Versuche",
+        Text::SortDateSet => "Schraubdatum",
+        Text::GroupSection => "Sektor",
+        Text::GroupGrade => "Grad",
+        Text::GroupColor => "Farbe",
+        Text::GroupNone => "keine",
+
+        Text::DayCutover => "Neuer Klettertag beginnt um",
+        Text::WeekStarts => "Wochenbeginn",
+        Text::Language => "Sprache",
+        Text::Climbers => "Kletternde",
+        Text::ClimberName => "Name",
+        Text::AddClimber => "Hinzufügen",
+        Text::ClimbersHint => {
+            "Füge alle hinzu, die auf diesem Gerät eintragen, um festzuhalten, wer jeden Tick geklettert ist. Ältere Ticks zählen für die erste Person."
+        }
+        Text::RemoveClimberPrompt => "{0} entfernen?",
+        Text::ClimberTicksGoTo => "Ihre {0} Ticks gehen an",
+        Text::ClimberTicksUnassigned => {
+            "Es gibt keine anderen Kletternden, ihre {0} Ticks sind danach niemandem zugeordnet."
+        }
+        Text::Cancel => "Abbrechen",
+        Text::LoggingFor => "Eintragen für",
+        Text::Weekday(weekday) => match weekday {
+            Weekday::Mon => "Mo",
+            Weekday::Tue => "Di",
+            Weekday::Wed => "Mi",
+            Weekday::Thu => "Do",
+            Weekday::Fri => "Fr",
+            Weekday::Sat => "Sa",
+            Weekday::Sun => "So",
+        },
+
+        Text::ExportData => "Daten exportieren",
+        Text::ImportData => "Daten importieren",
+        Text::ExportArchive => "Archiv exportieren",
+        Text::ArchiveNotAnArchive => "Diese Datei ist kein gymticks-Export.",
+        Text::ArchiveCompressed => {
+            "{0} ist komprimiert. Importiere das Archiv so, wie es exportiert wurde."
+        }
+        Text::ArchiveBadManifest => "Das Manifest des Archivs fehlt oder ist unlesbar.",
+        Text::ArchiveNewerVersion => {
+            "Das Archiv stammt von einer neueren gymticks-Version (Format {0})."
+        }
+        Text::ArchiveMissingFile => "Im Archiv fehlt {0}.",
+        Text::ArchiveDamaged => "{0} im Archiv ist beschädigt.",
+        Text::ArchiveBadData => "Die Daten in dieser Datei konnten nicht gelesen werden.",
+        Text::Passphrase => "Passphrase",
+        Text::PassphrasePlaceholder => "Leer lassen für einen unverschlüsselten Export",
+        Text::PassphraseConfirmation => "Passphrase für den Export wiederholen",
+        Text::PassphraseMismatch => "Die Passphrasen stimmen nicht überein.",
+        Text::DecryptAndImport => "Entschlüsseln und importieren",
+        Text::EncryptionPassphraseRequired => {
+            "Dieser Export ist verschlüsselt. Gib seine Passphrase ein."
+        }
+        Text::EncryptionWrongPassphrase => {
+            "Die Passphrase ist falsch oder die Datei wurde verändert."
+        }
+        Text::EncryptionUnsupportedVersion => {
+            "Dieser Export nutzt Verschlüsselungsversion {0}. Aktualisiere die App, um ihn zu importieren."
+        }
+        Text::EncryptionDamaged => "Der verschlüsselte Export ist beschädigt.",
+        Text::EncryptionFailed => "Das Verschlüsseln des Exports ist fehlgeschlagen.",
+        Text::Backups => "Sicherungen",
+        Text::BackupsCaption => {
+            "Auf diesem Gerät gespeichert: eine pro Tag für die letzte Woche und eine vor jedem Import oder Wiederherstellen."
+        }
+        Text::NoBackups => "Noch keine Sicherungen.",
+        Text::BackupDaily => "Tägliche Sicherung",
+        Text::BackupBeforeImport => "Vor dem Import",
+        Text::BackupBeforeRestore => "Vor dem Wiederherstellen",
+        Text::BackupCounts => "{0} Routen, {1} Ticks",
+        Text::BackupUnreadable => "Diese Sicherung konnte nicht gelesen werden.",
+        Text::SaveFailed => {
+            "Der Speicher auf diesem Gerät ist voll, deine letzten Änderungen sind nicht gespeichert. Exportiere deine Daten, um sie zu behalten."
+        }
+        Text::SnapshotsDropped => {
+            "Der Speicher auf diesem Gerät wird knapp, deshalb wurden ältere Sicherungen gelöscht. Exportiere deine Daten, um eine Kopie zu behalten."
+        }
+        Text::Restore => "Wiederherstellen",
+        Text::Delete => "Löschen",
+        Text::Duplicates => "Duplikate",
+        Text::DuplicatesCaption => {
+            "Aktuelle Routen mit gleichem Sektor, gleicher Farbe und gleichem Grad. Beim Zusammenführen landen alle Ticks auf einer davon."
+        }
+        Text::NoDuplicates => "Keine doppelten Routen.",
+        Text::RouteTicks => "{0} Ticks",
+        Text::Merge => "Zusammenführen",
+        Text::MergeOrSplit => "Zusammenführen oder aufteilen",
+        Text::MergeWith => "Mit einer anderen Route zusammenführen",
+        Text::ChooseRoute => "Route wählen\u{2026}",
+        Text::KeepTheseDetails => "Angaben dieser Route behalten",
+        Text::UseOtherDetails => "Angaben der anderen Route übernehmen",
+        Text::SplitCaption => "Ticks in eine neue Route verschieben",
+        Text::SplitOff => "{0} Ticks abtrennen",
+        Text::MergedRoutes => "{0} Routen zusammengeführt.",
+        Text::SplitRoute => "{0} Ticks in eine neue Route verschoben.",
+        Text::Undo => "Rückgängig",
+
+        Text::Activity => "Aktivität",
+        Text::DaysClimbed => "{0} Klettertage im letzten Jahr",
+        Text::DayTitleFormat => "%d.%m.%Y",
+        Text::DaySummary => "{0} dgs, {1} vsn",
+        Text::NoTicksOnDay => "Keine Einträge an diesem Tag.",
+        Text::SendsToday => "Durchstiege heute",
+        Text::SendsThisWeek => "Durchstiege diese Woche",
+        Text::SendsTotal => "Durchstiege insgesamt",
+        Text::Style => "Stil",
+        Text::Today => "Heute",
+        Text::ThisWeek => "Diese Woche",
+        Text::Total => "Gesamt",
+        Text::Climber => "Kletternde:r",
+        Text::Progress => "Fortschritt",
+        Text::LastWeeks => "Letzte {0} Wochen",
+        Text::HardestSend => "schwerster Durchstieg pro Woche",
+        Text::AverageGrade => "Durchschnitt über {0} Wochen",
+        Text::Walls => "Wände",
+        Text::WallsCaption => "Durchstiege an aktuellen Routen. Umrandete Wände haben Projekte.",
+        Text::GymMap => "Hallenplan",
+        Text::GymMapCaption => {
+            "Wähle eine Wand und tippe dann ihre Ecken auf dem Plan an. Wände ohne Umriss werden im Raster gewählt."
+        }
+        Text::ClearOutline => "Umriss löschen",
+        Text::DoneDrawing => "Fertig",
+
+        Text::Team => "Team",
+        Text::TeamCaption => "Rangliste und Challenges mit {0} Teammitgliedern",
+        Text::OpenTeam => "Öffnen",
+        Text::You => "Du",
+        Text::Leaderboard => "Rangliste",
+        Text::ThisMonth => "Dieser Monat. Ein Durchstieg zählt den Platz seines Grades in der Gradliste.",
+        Text::Points => "Punkte",
+        Text::Sends => "Durchstiege",
+        Text::Flashes => "Flashes",
+        Text::Challenges => "Challenges",
+        Text::ChallengeSectionSweep => "Jede Route in {0} klettern",
+        Text::ChallengeMonthlySends => "{0} Routen in diesem Monat klettern",
+        Text::ChallengeGradeSends => "{0} Routen im Grad {1} klettern",
+        Text::ChallengeKindSection => "Jede Route eines Bereichs",
+        Text::ChallengeKindMonthly => "Durchstiege in diesem Monat",
+        Text::ChallengeKindGrade => "Durchstiege eines Grades",
+        Text::AddChallenge => "Hinzufügen",
+        Text::Teammates => "Teammitglieder",
+        Text::TeammatesCaption => "Exporte, die dein Team mit dir geteilt hat. Sie bleiben auf diesem Gerät und sind nicht Teil deines Exports.",
+        Text::TeammateImported => "vor {0} importiert",
+        Text::AddTeammates => "Exporte des Teams hinzufügen",
+        Text::TeamStorageFull => "Auf diesem Gerät ist kein Platz mehr für diese Exporte.",
+        Text::TeammatesPassphrasePlaceholder => "Nur für verschlüsselte Exporte nötig",
+
+        Text::Goals => "Ziele",
+        Text::Sessions => "Sessions",
+        Text::GoalSends => "{1} {0} Routen klettern",
+        Text::GoalSendsGrade => "{1} {0} Routen ab {2} klettern",
+        Text::GoalFlashes => "{1} {0} Routen flashen",
+        Text::GoalFlashesGrade => "{1} {0} Routen ab {2} flashen",
+        Text::GoalSessions => "{1} {0} Sessions klettern",
+        Text::PeriodWeek => "Diese Woche",
+        Text::PeriodMonth => "Diesen Monat",
+        Text::PeriodQuarter => "Dieses Quartal",
+        Text::PeriodYear => "Dieses Jahr",
+        Text::PeriodEver => "",
+        Text::Once => "einmalig",
+        Text::AnyGrade => "jeder Grad",
+        Text::GradeOrHarder => "ab {0}",
+        Text::AddGoal => "Ziel hinzufügen",
+        Text::GoalReached => "Ziel erreicht: {0}",
+
+        Text::Trophies => "Trophäen",
+        Text::TrophiesCaption => "Meilensteine aus deinen Ticks. Einmal verdient, bleiben sie dir.",
+        Text::TrophiesCount => "{0} verdient",
+        Text::AllTrophies => "Alle Trophäen",
+        Text::NextUp => "Als Nächstes",
+        Text::NoTrophies => "Klettere eine Route, um deine erste Trophäe zu verdienen.",
+        Text::AchievementUnlocked => "Trophäe verdient: {0}",
+        Text::AchievementFirstSend => "Erste {0}",
+        Text::AchievementFirstSendDescription => "Erster Durchstieg eines neuen Grades",
+        Text::AchievementSends => "{0} Durchstiege",
+        Text::AchievementSendsDescription => "Durchstiege insgesamt",
+        Text::AchievementStreak => "{0} Sessions in Folge",
+        Text::AchievementStreakDescription => "Sessions in Folge mit einem Durchstieg",
+        Text::AchievementSectionColors => "Jede Farbe in {0}",
+        Text::AchievementSectionColorsDescription => "Jede Farbe eines Bereichs geklettert",
+
+        Text::Now => "jetzt",
+        Text::Minutes => "{0}min",
+        Text::Hours => "{0}h",
+        Text::Days => "{0}T",
+        Text::Weeks => "{0}W",
+        Text::Months => "{0}M",
+        Text::Years => "{0}J",
+
+        Text::KeyboardShortcuts => "Tastenkürzel",
+        Text::ShortcutNewRoute => "Neue Route",
+        Text::ShortcutSearch => "Routen suchen",
+        Text::ShortcutNext => "Nächste Route",
+        Text::ShortcutPrevious => "Vorherige Route",
+        Text::ShortcutSend => "Durchstieg eintragen",
+        Text::ShortcutAttempt => "Versuch eintragen",
+        Text::ShortcutEdit => "Route bearbeiten",
+        Text::ShortcutClose => "Schließen",
+        Text::ShortcutHelp => "Diese Kürzel anzeigen",
+
+        Text::QuickLog => "Loggen",
+        Text::QuickLogPrompt => "Wähle Sektor, Farbe und Grad.",
+        Text::QuickLogOn => "Eintrag für {0}.",
+        Text::QuickLogNewRoute => "Keine passende Route. Beim Eintragen wird eine angelegt.",
+        Text::QuickLogged => "{0} für {1} eingetragen, heute {2}.",
+
+        Text::CreatedBy => "erstellt von ",
+        Text::With => " mit ",
+        Text::PwaTapHere => "Hier tippen und \"Zum Home-Bildschirm\" wählen.",
+        Text::PwaAddToHomeScreen => "Füge diese App zum Home-Bildschirm hinzu.",
+        Text::PwaIphoneReason => "Dann löscht dein iPhone deine Daten weniger wahrscheinlich.",
+        Text::PwaReason => "So ist es besser!",
+        Text::UpdateAvailable => "Eine neue Version von gymticks ist bereit.",
+        Text::Reload => "Neu laden",
+
+        Text::About => "Über",
+        Text::AppVersion => "gymticks {0}",
+        Text::Offline => "Offline-Nutzung",
+        Text::WorkerRegistering => "Wird eingerichtet…",
+        Text::WorkerRegistered => "Bereit",
+        Text::WorkerUnsupported => "In diesem Browser nicht verfügbar",
+        Text::WorkerFailed => "Konnte nicht eingerichtet werden: {0}",
+        Text::Storage => "Speicher",
+        Text::StoragePersisted => "Bleibt erhalten, auch wenn der Speicher knapp wird",
+        Text::StorageNotPersisted => "Der Browser kann ihn löschen, wenn der Speicher knapp wird",
+        Text::StorageUsage => "{0} von {1} belegt",
+        Text::LocalStorageUsage => "Ticks und Sicherungen belegen {0} von etwa {1}",
+        Text::KeepMyData => "Daten behalten",
+        Text::Unknown => "Unbekannt",
+        Text::LastExport => "Letzter Export",
+        Text::LastExportAge => "vor {0}",
+        Text::NoExportYet => "Noch keiner",
+    }
+}
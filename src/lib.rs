@@ -2,50 +2,283 @@
 extern crate indexmap;
 extern crate serde_json;
 
-use chrono::{DateTime, Local, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc, Weekday};
 use enclose::enc;
 use indexmap::IndexMap;
 use itertools::Itertools;
 use seed::{prelude::*, *};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::mem;
 use uuid::Uuid;
 use apply::Apply;
 
+mod achievement;
+mod archive;
+mod backup;
+mod charts;
+mod climber;
 mod color;
+pub mod encryption;
+mod filter;
+mod goal;
 mod grade;
+mod gym_map;
+mod health;
+mod heatmap;
+mod i18n;
+mod merge;
+mod photo;
+mod quick_log;
 mod section;
+mod shortcuts;
+mod sort;
+mod team;
+mod tick_style;
 mod util;
 
+use crate::achievement::{Achievement, Unlocked};
+use crate::archive::{ArchiveError, ArchivePhoto};
+use crate::backup::{SaveOutcome, Snapshot};
+use crate::climber::{Climber, Removal};
 use crate::color::Color;
+use crate::encryption::EncryptionError;
+use crate::filter::{Filter, FilterToggle, UNTOUCHED_DAYS};
+use crate::goal::{Goal, GoalDraft, GoalKind, Period};
 use crate::grade::Grade;
+use crate::health::{Health, StorageStatus, WorkerStatus};
+use crate::i18n::{Locale, Text};
+use crate::merge::{RouteTools, Undo};
+use crate::photo::{HoldCircle, Photo};
+use crate::quick_log::QuickLog;
 use crate::section::Section;
+use crate::shortcuts::Shortcut;
+use crate::sort::{Grouping, SortOrder};
+use crate::team::{Challenge, ChallengeDraft, ChallengeKind, TeamError, Teammate};
+use crate::tick_style::TickStyle;
 
 const ENTER_KEY: u32 = 13;
 const STORAGE_KEY: &str = "gymticks-11";
+const STRIPPING_SOON_DAYS: i64 = 14;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_DAY_CUTOVER_HOUR: u32 = 4;
+
+const PWA: &str = "pwa";
+const ROUTE: &str = "route";
+const STATS: &str = "stats";
+const SETTINGS: &str = "settings";
+const RETIRED: &str = "retired";
+const LOG: &str = "log";
+const DATA: &str = "data";
+const DUPLICATES: &str = "duplicates";
+const BACKUPS: &str = "backups";
+const TEAM: &str = "team";
+const TROPHIES: &str = "trophies";
+const DAY: &str = "day";
 
 type RouteId = Uuid;
 
 struct Model {
+    base_url: Url,
+    page: Page,
     persisted: PersistedData,
     data: Data,
 }
 
+enum Page {
+    Routes,
+    Route(RouteId),
+    QuickLog,
+    Stats,
+    Team,
+    Trophies,
+    Settings,
+    Retired,
+    Data,
+    Backups,
+    Duplicates,
+    Day(NaiveDate),
+    NotFound,
+}
+
+impl Page {
+    fn init(mut url: Url) -> Self {
+        match url.next_path_part() {
+            None | Some("") | Some(PWA) => Self::Routes,
+            Some(ROUTE) => url
+                .next_path_part()
+                .and_then(|id| RouteId::parse_str(id).ok())
+                .map_or(Self::NotFound, Self::Route),
+            Some(LOG) => Self::QuickLog,
+            Some(STATS) => Self::Stats,
+            Some(TEAM) => Self::Team,
+            Some(TROPHIES) => Self::Trophies,
+            Some(SETTINGS) => Self::Settings,
+            Some(RETIRED) => Self::Retired,
+            Some(DATA) => Self::Data,
+            Some(BACKUPS) => Self::Backups,
+            Some(DUPLICATES) => Self::Duplicates,
+            Some(DAY) => url
+                .next_path_part()
+                .and_then(parse_date)
+                .map_or(Self::NotFound, Self::Day),
+            Some(_) => Self::NotFound,
+        }
+    }
+}
+
+struct_urls!();
+impl<'a> Urls<'a> {
+    fn routes(self) -> Url {
+        self.base_url()
+    }
+    fn route(self, route_id: &RouteId) -> Url {
+        self.base_url()
+            .add_path_part(ROUTE)
+            .add_path_part(route_id.to_string())
+    }
+    fn quick_log(self) -> Url {
+        self.base_url().add_path_part(LOG)
+    }
+    fn stats(self) -> Url {
+        self.base_url().add_path_part(STATS)
+    }
+    fn team(self) -> Url {
+        self.base_url().add_path_part(TEAM)
+    }
+    fn trophies(self) -> Url {
+        self.base_url().add_path_part(TROPHIES)
+    }
+    fn settings(self) -> Url {
+        self.base_url().add_path_part(SETTINGS)
+    }
+    fn retired(self) -> Url {
+        self.base_url().add_path_part(RETIRED)
+    }
+    fn data(self) -> Url {
+        self.base_url().add_path_part(DATA)
+    }
+    fn backups(self) -> Url {
+        self.base_url().add_path_part(BACKUPS)
+    }
+    fn duplicates(self) -> Url {
+        self.base_url().add_path_part(DUPLICATES)
+    }
+    fn day(self, date: NaiveDate) -> Url {
+        self.base_url()
+            .add_path_part(DAY)
+            .add_path_part(date.format(DATE_FORMAT).to_string())
+    }
+}
+
 #[derive(Default, Serialize, Deserialize)]
 struct PersistedData {
     routes: IndexMap<RouteId, Route>,
     settings: Settings,
+    #[serde(default)]
+    challenges: Vec<Challenge>,
+    #[serde(default)]
+    goals: Vec<Goal>,
+    #[serde(default)]
+    achievements: Vec<Unlocked>,
 }
 
 #[derive(Default, Serialize, Deserialize)]
 struct Data {
     new_route_title: String,
+    new_route_beta: String,
+    new_tick_note: String,
     editing_route: Option<RouteId>,
     chosen_color: String,
     chosen_section: String,
     chosen_grade: String,
+    chosen_styles: Vec<String>,
+    chosen_rating: Option<u8>,
+    chosen_grade_opinion: Option<GradeOpinion>,
+    chosen_project: bool,
+    chosen_set_on: String,
+    chosen_expected_strip: String,
     modal_open: bool,
-    pwa_ad: bool
+    pwa_ad: bool,
+    new_climber_name: String,
+    #[serde(skip)]
+    removing_climber: Option<Removal>,
+    /// The section whose outline is being drawn on the gym map.
+    #[serde(skip)]
+    drawing_outline: Option<String>,
+    #[serde(skip)]
+    filter: Filter,
+    filter_open: bool,
+    /// Object URLs of route photos loaded from IndexedDB.
+    #[serde(skip)]
+    photo_urls: HashMap<RouteId, String>,
+    #[serde(skip)]
+    import_error: Option<ArchiveError>,
+    /// Encrypts exports when set. Never saved.
+    #[serde(skip)]
+    passphrase: String,
+    /// The passphrase typed again, which has to match before an encrypted export.
+    #[serde(skip)]
+    passphrase_confirmation: String,
+    /// An encrypted import waiting for the right passphrase.
+    #[serde(skip)]
+    pending_import: Option<Vec<u8>>,
+    #[serde(skip)]
+    backups: Vec<Snapshot>,
+    #[serde(skip)]
+    restore_failed: bool,
+    /// Storage was too full for the last save, even without any snapshots.
+    #[serde(skip)]
+    save_failed: bool,
+    /// Snapshots were deleted, or not taken, because storage was too full.
+    #[serde(skip)]
+    snapshots_dropped: bool,
+    /// The route keyboard shortcuts act on in the route list.
+    #[serde(skip)]
+    focused_route: Option<RouteId>,
+    #[serde(skip)]
+    shortcut_help: bool,
+    #[serde(skip)]
+    quick_log: QuickLog,
+    /// A route already up with the new route's section, color and grade, and the tick the
+    /// new route was going to get.
+    #[serde(skip)]
+    duplicate_prompt: Option<(RouteId, Option<TickType>)>,
+    #[serde(skip)]
+    route_tools: RouteTools,
+    #[serde(skip)]
+    undo: Option<Undo>,
+    /// Teammates' exports, kept apart from `PersistedData` so they never end up in ours.
+    #[serde(skip)]
+    team: Vec<Teammate>,
+    #[serde(skip)]
+    team_error: Option<TeamError>,
+    /// Decrypts teammates' exports, apart from `passphrase` so it never encrypts ours.
+    #[serde(skip)]
+    team_passphrase: String,
+    #[serde(skip)]
+    challenge_draft: ChallengeDraft,
+    #[serde(skip)]
+    goal_draft: GoalDraft,
+    /// Goals reached since they were last dismissed.
+    #[serde(skip)]
+    completed_goals: Vec<String>,
+    /// Achievements the last tick unlocked.
+    #[serde(skip)]
+    celebration: Vec<Achievement>,
+    /// A new version's service worker, installed and waiting for a reload.
+    #[serde(skip)]
+    update: Option<web_sys::ServiceWorker>,
+    #[serde(skip)]
+    health: Health,
+}
+
+impl Data {
+    /// Whether an encrypted export's passphrase has been typed the same way twice. Unencrypted
+    /// exports don't need it.
+    fn passphrase_confirmed(&self) -> bool {
+        self.passphrase.is_empty() || self.passphrase == self.passphrase_confirmation
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -53,6 +286,33 @@ struct Settings {
     grades: IndexMap<String, Grade>,
     sections: IndexMap<String, Section>,
     colors: IndexMap<String, Color>,
+    #[serde(default = "TickStyle::defaults")]
+    tick_styles: IndexMap<String, TickStyle>,
+    #[serde(default)]
+    sort_order: SortOrder,
+    #[serde(default)]
+    grouping: Grouping,
+    #[serde(default = "default_day_cutover_hour")]
+    day_cutover_hour: u32,
+    #[serde(default = "default_week_start")]
+    week_start: Weekday,
+    /// Unset until the first run, when it's filled in from the browser's language.
+    #[serde(default)]
+    locale: Option<Locale>,
+    /// Empty while one person uses the app.
+    #[serde(default)]
+    climbers: IndexMap<String, Climber>,
+    /// Who new ticks are logged for.
+    #[serde(default)]
+    active_climber: Option<String>,
+}
+
+fn default_day_cutover_hour() -> u32 {
+    DEFAULT_DAY_CUTOVER_HOUR
+}
+
+fn default_week_start() -> Weekday {
+    Weekday::Mon
 }
 
 impl Default for Settings {
@@ -60,12 +320,56 @@ impl Default for Settings {
         Self {
             colors: Color::defaults(),
             sections: Section::defaults(),
-            grades: Grade::defaults()
+            grades: Grade::defaults(),
+            tick_styles: TickStyle::defaults(),
+            sort_order: SortOrder::default(),
+            grouping: Grouping::default(),
+            day_cutover_hour: default_day_cutover_hour(),
+            week_start: default_week_start(),
+            locale: None,
+            climbers: IndexMap::new(),
+            active_climber: None,
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl Settings {
+    fn locale(&self) -> Locale {
+        self.locale.unwrap_or_default()
+    }
+
+    /// Who climbed a tick. Ticks logged before any climbers were added count for the first.
+    fn tick_climber<'a>(&'a self, tick: &'a Tick) -> Option<&'a String> {
+        tick.climber
+            .as_ref()
+            .or_else(|| self.climbers.keys().next())
+    }
+
+    fn climbing_day(&self, timestamp: i64) -> NaiveDate {
+        util::climbing_day(timestamp, &Local, self.day_cutover_hour)
+    }
+
+    fn today(&self) -> NaiveDate {
+        self.climbing_day(Utc::now().timestamp())
+    }
+
+    fn week_start(&self, date: NaiveDate) -> NaiveDate {
+        util::week_start(date, self.week_start)
+    }
+
+    fn tick_styles_for_grade<'a>(
+        &'a self,
+        grade: &'a str,
+    ) -> impl Iterator<Item = (&'a String, &'a TickStyle)> {
+        let grade = self.grades.get(grade);
+
+        self.tick_styles
+            .iter()
+            .filter(move |(_k, style)| style.applies_to(grade))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
 struct Route {
     title: String,
     completed: bool,
@@ -74,12 +378,66 @@ struct Route {
     grade: String,
     ticks: Vec<Tick>,
     retired: bool,
+    #[serde(default)]
+    beta: String,
+    #[serde(default)]
+    rating: Option<u8>,
+    #[serde(default)]
+    grade_opinion: Option<GradeOpinion>,
+    #[serde(default)]
+    project: bool,
+    #[serde(default)]
+    created_at: i64,
+    #[serde(default)]
+    set_on: Option<NaiveDate>,
+    #[serde(default)]
+    expected_strip: Option<NaiveDate>,
+    #[serde(default)]
+    photo: Option<Photo>,
+}
+
+impl Route {
+    /// The title, or a description for routes added without one.
+    fn name(&self) -> String {
+        if self.title.is_empty() {
+            format!("{} {} {}", self.section, self.color, self.grade)
+        } else {
+            self.title.clone()
+        }
+    }
+
+    /// When the route went up. Falls back to when it was added here, and then to its first
+    /// tick for routes created before either was recorded.
+    fn set_timestamp(&self) -> Option<i64> {
+        self.set_on
+            .map(|date| util::climbing_day_start(date, &Local, 0))
+            .or(Some(self.created_at).filter(|created_at| *created_at > 0))
+            .or_else(|| self.ticks.iter().map(|tick| tick.timestamp).min())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct Tick {
     typ: TickType,
     timestamp: i64,
+    #[serde(default)]
+    styles: Vec<String>,
+    #[serde(default)]
+    note: String,
+    /// A key into `Settings::climbers`.
+    #[serde(default)]
+    climber: Option<String>,
+}
+
+impl Tick {
+    /// A send logged with the flash or onsight style.
+    fn is_flash(&self) -> bool {
+        matches!(self.typ, TickType::Ascent)
+            && self
+                .styles
+                .iter()
+                .any(|style| tick_style::FLASH_STYLES.contains(&style.as_str()))
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -88,30 +446,98 @@ enum TickType {
     Attempt = 0x01,
 }
 
+impl TickType {
+    fn label(&self) -> Text {
+        match self {
+            TickType::Ascent => Text::Send,
+            TickType::Attempt => Text::Attempt,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+enum GradeOpinion {
+    Soft,
+    Hard,
+}
+
+impl GradeOpinion {
+    fn label(self) -> Text {
+        match self {
+            GradeOpinion::Soft => Text::Soft,
+            GradeOpinion::Hard => Text::Hard,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 struct EditingRoute {
     id: RouteId,
     title: String,
 }
 
-fn init(mut url: Url, orders: &mut impl Orders<Msg>) -> Model {
+fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
     // TODO we should use defaults if the data is not present, but show some sort of
     // error message if the data is mangled.
 
-    let worker_container = window().navigator().service_worker();
+    let worker_status = if health::workers_supported() {
+        let worker_container = window().navigator().service_worker();
+        orders.perform_cmd(async move {
+            // The Workbox worker used to be registered from /public/, and would go on serving
+            // stale copies of everything under it.
+            if let Ok(registrations) = JsFuture::from(worker_container.get_registrations()).await {
+                for registration in js_sys::Array::from(&registrations).iter() {
+                    let registration: web_sys::ServiceWorkerRegistration =
+                        registration.unchecked_into();
+
+                    if registration.scope().ends_with("/public/") {
+                        let _ = registration.unregister();
+                    }
+                }
+            }
+
+            let options = web_sys::RegistrationOptions::new();
+            // The precache manifest the worker imports is what changes between builds.
+            options.set_update_via_cache(web_sys::ServiceWorkerUpdateViaCache::None);
+
+            let registration = worker_container
+                .register_with_options("/service-worker.js", &options)
+                .apply(JsFuture::from)
+                .await;
+
+            Msg::WorkerRegistered(registration.map_err(|error| health::error_message(&error)))
+        });
+
+        WorkerStatus::Registering
+    } else {
+        WorkerStatus::Unsupported
+    };
+
+    orders.subscribe(Msg::UrlChanged);
+    orders.stream(streams::window_event(Ev::KeyDown, |event| {
+        Msg::KeyPressed(event.unchecked_into())
+    }));
+
+    let mut persisted: PersistedData = LocalStorage::get(STORAGE_KEY).unwrap_or_default();
+    persisted.settings.locale.get_or_insert_with(Locale::detect);
+    let (backups, snapshots_dropped) = backup::take_daily(&persisted);
+    // Whatever was earned before now isn't news.
+    achievement::record(&mut persisted);
+
+    // Only ask the browser to keep our storage once there's something worth keeping.
+    let ask_to_persist = !persisted.routes.is_empty();
     orders.perform_cmd(async move {
-        // I don't currently care if this succeeds or know
-        // why it would fail.
-        worker_container
-            .register("/public/service-worker.js")
-            .apply(JsFuture::from)
-            .await;
-        ()
+        let status = JsFuture::from(checkStorage(ask_to_persist)).await.ok()?;
+        Some(Msg::StorageChecked(StorageStatus::from_js(&status)))
+    });
+    orders.perform_cmd(async {
+        let json = JsFuture::from(loadTeam()).await.ok()?.as_string()?;
+        Some(Msg::TeamLoaded(json))
     });
 
-    let persisted: PersistedData = LocalStorage::get(STORAGE_KEY).unwrap_or_default();
+    let filter = Filter::from_url_search(url.search());
 
-    let pwa_ad = if let Some("pwa") = url.next_path_part() {
+    let pwa_ad = if let Some(PWA) = url.path().first().map(String::as_str) {
         false
     } else {
         true
@@ -143,12 +569,58 @@ fn init(mut url: Url, orders: &mut impl Orders<Msg>) -> Model {
             .0
             .to_string(),
         new_route_title: "".to_string(),
+        new_route_beta: "".to_string(),
+        new_tick_note: "".to_string(),
         editing_route: None,
+        chosen_styles: Vec::new(),
+        chosen_rating: None,
+        chosen_grade_opinion: None,
+        chosen_project: false,
+        chosen_set_on: "".to_string(),
+        chosen_expected_strip: "".to_string(),
         modal_open: false,
         pwa_ad,
+        new_climber_name: String::new(),
+        removing_climber: None,
+        drawing_outline: None,
+        filter,
+        filter_open: false,
+        photo_urls: HashMap::new(),
+        import_error: None,
+        passphrase: String::new(),
+        passphrase_confirmation: String::new(),
+        pending_import: None,
+        backups,
+        restore_failed: false,
+        save_failed: false,
+        snapshots_dropped,
+        focused_route: None,
+        shortcut_help: false,
+        quick_log: QuickLog::default(),
+        duplicate_prompt: None,
+        route_tools: RouteTools::default(),
+        undo: None,
+        team: Vec::new(),
+        team_error: None,
+        team_passphrase: String::new(),
+        challenge_draft: ChallengeDraft::default(),
+        goal_draft: GoalDraft::default(),
+        completed_goals: Vec::new(),
+        celebration: Vec::new(),
+        update: None,
+        health: Health::load(worker_status),
     };
 
-    Model { persisted, data }
+    let model = Model {
+        base_url: url.to_base_url(),
+        page: Page::init(url),
+        persisted,
+        data,
+    };
+
+    load_photo(&model, orders);
+
+    model
 }
 
 // ------ ------
@@ -157,38 +629,231 @@ fn init(mut url: Url, orders: &mut impl Orders<Msg>) -> Model {
 
 #[derive(Clone)]
 enum Msg {
+    UrlChanged(subs::UrlChanged),
+    OpenDay(NaiveDate),
+    OpenSection(String),
+
     NewRouteTitleChanged(String),
+    NewRouteBetaChanged(String),
+    NewTickNoteChanged(String),
 
     CreateNewRoute(Option<TickType>),
+    ConfirmNewRoute(Option<TickType>),
+    UseExistingRoute,
 
     StartRouteEdit(RouteId),
     SaveEditingRoute,
     RetireEditingRoute,
-
-    AddTickToRoute(RouteId, TickType),
+    UnretireRoute(RouteId),
+    AddPhoto(RouteId),
+    PhotoAdded(RouteId, String),
+    PhotoLoaded(RouteId, String),
+    RemovePhoto(RouteId),
+    AddHoldCircle(RouteId, f64, f64),
+    RemoveHoldCircle(RouteId, usize),
+
+    AddTickToRoute(RouteId, TickType, Vec<String>, String),
+    ToggleTickStyle(RouteId, String),
+    TickNoteChanged(RouteId, usize, String),
 
     ChooseColor(String),
     ChooseSection(String),
     ChooseGrade(String),
+    ToggleStyle(String),
+    ChooseRating(u8),
+    ChooseGradeOpinion(GradeOpinion),
+    ToggleProject,
+    SetOnChanged(String),
+    ExpectedStripChanged(String),
 
     OpenModal(),
     CloseModal(),
 
+    FilterQueryChanged(String),
+    ToggleFilter(FilterToggle),
+    ToggleFilterPanel,
+    ClearFilter,
+
+    ChooseSortOrder(SortOrder),
+    ChooseGrouping(Grouping),
+    ChooseDayCutover(String),
+    ChooseWeekStart(Weekday),
+    ChooseLocale(Locale),
+    NewClimberNameChanged(String),
+    AddClimber,
+    StartRemoveClimber(String),
+    ChooseClimberHeir(String),
+    RemoveClimber,
+    CancelRemoveClimber,
+    ChooseClimber(String),
+    DrawOutline(Option<String>),
+    AddOutlinePoint(i32, i32),
+    ClearOutline(String),
+
     ExportData(),
+    ExportArchive(),
+    ExportFailed(EncryptionError),
     StartImportData(),
     ImportData(String),
+    ImportArchive(Vec<u8>),
+    PassphraseChanged(String),
+    PassphraseConfirmationChanged(String),
+    RetryImport,
+    RestoreBackup(String),
+    MergeDuplicates(Vec<RouteId>),
+    ToggleRouteTools,
+    ChooseMergeRoute(Option<RouteId>),
+    ChooseMergeDetails(bool),
+    MergeRoute(RouteId),
+    ToggleSplitTick(usize),
+    SplitRoute(RouteId),
+    Undo,
+    DismissUndo,
+    DeleteBackup(String),
+    DismissSnapshotsDropped,
+
+    AddTeammates,
+    TeammatesLoaded(Vec<(String, Vec<u8>)>),
+    TeamLoaded(String),
+    TeamSaveFailed,
+    TeamPassphraseChanged(String),
+    RemoveTeammate(usize),
+    ChooseChallengeKind(ChallengeKind),
+    ChallengeSectionChanged(String),
+    ChallengeGradeChanged(String),
+    ChallengeCountChanged(String),
+    AddChallenge,
+    RemoveChallenge(usize),
+
+    ChooseGoalKind(GoalKind),
+    GoalGradeChanged(String),
+    GoalCountChanged(String),
+    ChooseGoalPeriod(Period),
+    AddGoal,
+    RemoveGoal(usize),
+    DismissCompletedGoals,
+    DismissCelebration,
+
+    WorkerRegistered(Result<JsValue, String>),
+    UpdateAvailable(web_sys::ServiceWorker),
+    ReloadForUpdate,
+    StorageChecked(StorageStatus),
+    RequestPersistentStorage,
+
+    QuickChooseSection(String),
+    QuickChooseColor(String),
+    QuickChooseGrade(String),
+    QuickChooseRoute(RouteId),
+    QuickTick(TickType),
+
+    KeyPressed(web_sys::KeyboardEvent),
+    ToggleShortcutHelp,
 
     NoOp,
 }
 
+impl Msg {
+    /// Whether handling the message can change the routes or their ticks.
+    fn changes_routes(&self) -> bool {
+        matches!(
+            self,
+            Msg::ConfirmNewRoute(_)
+                | Msg::SaveEditingRoute
+                | Msg::RetireEditingRoute
+                | Msg::UnretireRoute(_)
+                | Msg::PhotoAdded(..)
+                | Msg::RemovePhoto(_)
+                | Msg::AddHoldCircle(..)
+                | Msg::RemoveHoldCircle(..)
+                | Msg::AddTickToRoute(..)
+                | Msg::ToggleTickStyle(..)
+                | Msg::TickNoteChanged(..)
+                | Msg::ImportData(_)
+                | Msg::ImportArchive(_)
+                | Msg::RetryImport
+                | Msg::RestoreBackup(_)
+                | Msg::MergeDuplicates(_)
+                | Msg::MergeRoute(_)
+                | Msg::SplitRoute(_)
+                | Msg::Undo
+                | Msg::QuickTick(_)
+                | Msg::RemoveClimber
+        )
+    }
+}
+
 fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
+    // Only a new tick is worth celebrating. Imports, restores and new climbers unlock
+    // achievements quietly.
+    let ticked = matches!(msg, Msg::AddTickToRoute(..));
+
+    let changes_routes = msg.changes_routes();
+    let adds_goal = matches!(msg, Msg::AddGoal);
+    let changes_climbers = matches!(msg, Msg::AddClimber | Msg::RemoveClimber);
+
+    // Undo puts back routes as they were, so it would throw away any change made since.
+    // Merging and splitting set a new one.
+    let dropped_undo =
+        changes_routes && !matches!(msg, Msg::Undo) && model.data.undo.take().is_some();
+
     match msg {
+        Msg::UrlChanged(subs::UrlChanged(url)) => {
+            model.page = Page::init(url);
+            model.data.route_tools = RouteTools::default();
+            if matches!(model.page, Page::Settings) {
+                model.data.health.local_storage = health::local_storage_size();
+            }
+            load_photo(model, orders);
+        }
+
+        Msg::OpenDay(date) => {
+            let url = Urls::new(&model.base_url).day(date);
+            url.go_and_push();
+            orders.notify(subs::UrlChanged(url));
+        }
+
+        Msg::OpenSection(section) => {
+            model.data.filter = Filter {
+                sections: vec![section],
+                ..Filter::default()
+            };
+
+            let url = Urls::new(&model.base_url)
+                .routes()
+                .set_search(model.data.filter.to_url_search());
+            url.go_and_push();
+            orders.notify(subs::UrlChanged(url));
+        }
+
         Msg::NewRouteTitleChanged(title) => {
             model.data.new_route_title = title;
         }
 
+        Msg::NewRouteBetaChanged(beta) => {
+            model.data.new_route_beta = beta;
+        }
+
+        Msg::NewTickNoteChanged(note) => {
+            model.data.new_tick_note = note;
+        }
+
         Msg::CreateNewRoute(tick_type) => {
+            match merge::find_duplicate(
+                &model.persisted.routes,
+                &model.data.chosen_section,
+                &model.data.chosen_color,
+                &model.data.chosen_grade,
+            ) {
+                Some(route_id) => model.data.duplicate_prompt = Some((route_id, tick_type)),
+                None => {
+                    orders.send_msg(Msg::ConfirmNewRoute(tick_type));
+                }
+            }
+        }
+
+        Msg::ConfirmNewRoute(tick_type) => {
             let id = RouteId::new_v4();
+            model.data.duplicate_prompt = None;
 
             model.persisted.routes.insert(
                 id,
@@ -200,37 +865,32 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                     section: model.data.chosen_section.clone(),
                     grade: model.data.chosen_grade.clone(),
                     retired: false,
+                    beta: mem::take(&mut model.data.new_route_beta),
+                    rating: model.data.chosen_rating,
+                    grade_opinion: model.data.chosen_grade_opinion,
+                    project: model.data.chosen_project,
+                    created_at: Utc::now().timestamp(),
+                    set_on: parse_date(&model.data.chosen_set_on),
+                    expected_strip: parse_date(&model.data.chosen_expected_strip),
+                    photo: None,
                 },
             );
 
             if let Some(tick_type) = tick_type {
-                orders.send_msg(Msg::AddTickToRoute(id, tick_type));
+                let (styles, note) = take_chosen_tick(model);
+
+                orders.send_msg(Msg::AddTickToRoute(id, tick_type, styles, note));
             };
 
-            let settings = &model.persisted.settings;
+            model.data.modal_open = false;
+        }
 
-            model.persisted.routes.sort_by(|_ak, av, _bk, bv| {
-                settings
-                    .sections
-                    .get(&av.section)
-                    .map_or(0i32, |s| s.sort)
-                    .cmp(&settings.sections.get(&bv.section).map_or(0i32, |s| s.sort))
-                    .then(
-                        settings
-                            .colors
-                            .get(&av.color)
-                            .map_or(0i32, |s| s.sort)
-                            .cmp(&settings.colors.get(&bv.color).map_or(0i32, |s| s.sort)),
-                    )
-                    .then(
-                        settings
-                            .grades
-                            .get(&av.grade)
-                            .map_or(0i32, |s| s.sort)
-                            .cmp(&settings.grades.get(&bv.grade).map_or(0i32, |s| s.sort)),
-                    )
-                    .then(av.title.cmp(&bv.title))
-            });
+        Msg::UseExistingRoute => {
+            if let Some((route_id, Some(tick_type))) = model.data.duplicate_prompt.take() {
+                let (styles, note) = take_chosen_tick(model);
+
+                orders.send_msg(Msg::AddTickToRoute(route_id, tick_type, styles, note));
+            }
 
             model.data.modal_open = false;
         }
@@ -242,6 +902,12 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                 model.data.chosen_section = route.section.clone();
                 model.data.chosen_grade = route.grade.clone();
                 model.data.new_route_title = route.title.clone();
+                model.data.new_route_beta = route.beta.clone();
+                model.data.chosen_rating = route.rating;
+                model.data.chosen_grade_opinion = route.grade_opinion;
+                model.data.chosen_project = route.project;
+                model.data.chosen_set_on = format_date(route.set_on);
+                model.data.chosen_expected_strip = format_date(route.expected_strip);
             }
 
             model.data.modal_open = true;
@@ -253,32 +919,12 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
                     route.color = model.data.chosen_color.clone();
                     route.section = model.data.chosen_section.clone();
                     route.grade = model.data.chosen_grade.clone();
-
-                    // TODO: this code is duplicated.
-                    let settings = &model.persisted.settings;
-
-                    model.persisted.routes.sort_by(|_ak, av, _bk, bv| {
-                        settings
-                            .sections
-                            .get(&av.section)
-                            .map_or(0i32, |s| s.sort)
-                            .cmp(&settings.sections.get(&bv.section).map_or(0i32, |s| s.sort))
-                            .then(
-                                settings
-                                    .colors
-                                    .get(&av.color)
-                                    .map_or(0i32, |s| s.sort)
-                                    .cmp(&settings.colors.get(&bv.color).map_or(0i32, |s| s.sort)),
-                            )
-                            .then(
-                                settings
-                                    .grades
-                                    .get(&av.grade)
-                                    .map_or(0i32, |s| s.sort)
-                                    .cmp(&settings.grades.get(&bv.grade).map_or(0i32, |s| s.sort)),
-                            )
-                            .then(av.title.cmp(&bv.title))
-                    });
+                    route.beta = mem::take(&mut model.data.new_route_beta);
+                    route.rating = model.data.chosen_rating;
+                    route.grade_opinion = model.data.chosen_grade_opinion;
+                    route.project = model.data.chosen_project;
+                    route.set_on = parse_date(&model.data.chosen_set_on);
+                    route.expected_strip = parse_date(&model.data.chosen_expected_strip);
                 }
             }
 
@@ -295,29 +941,172 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             model.data.modal_open = false;
             model.data.editing_route = None;
         }
+        Msg::UnretireRoute(route_id) => {
+            if let Some(route) = model.persisted.routes.get_mut(&route_id) {
+                route.retired = false;
+            }
+        }
+
+        Msg::AddPhoto(route_id) => {
+            orders.perform_cmd(async move {
+                let url = JsFuture::from(startAddPhoto(route_id.to_string()))
+                    .await
+                    .ok()?
+                    .as_string()?;
+
+                Some(Msg::PhotoAdded(route_id, url))
+            });
+        }
+
+        Msg::PhotoAdded(route_id, url) => {
+            if let Some(route) = model.persisted.routes.get_mut(&route_id) {
+                route.photo = Some(Photo::default());
+            }
+
+            model.data.photo_urls.insert(route_id, url);
+        }
+
+        Msg::PhotoLoaded(route_id, url) => {
+            model.data.photo_urls.insert(route_id, url);
+        }
+
+        Msg::RemovePhoto(route_id) => {
+            if let Some(route) = model.persisted.routes.get_mut(&route_id) {
+                route.photo = None;
+            }
+
+            model.data.photo_urls.remove(&route_id);
+            deletePhoto(route_id.to_string());
+        }
+
+        Msg::AddHoldCircle(route_id, x, y) => {
+            if let Some(photo) = model
+                .persisted
+                .routes
+                .get_mut(&route_id)
+                .and_then(|route| route.photo.as_mut())
+            {
+                photo.holds.push(HoldCircle { x, y });
+            }
+        }
+
+        Msg::RemoveHoldCircle(route_id, index) => {
+            if let Some(photo) = model
+                .persisted
+                .routes
+                .get_mut(&route_id)
+                .and_then(|route| route.photo.as_mut())
+            {
+                if index < photo.holds.len() {
+                    photo.holds.remove(index);
+                }
+            }
+        }
 
-        Msg::AddTickToRoute(route_id, typ) => {
+        Msg::AddTickToRoute(route_id, typ, styles, note) => {
             if let Some(route) = model.persisted.routes.get_mut(&route_id) {
                 let timestamp = Utc::now().timestamp();
-                route.ticks.push(Tick { typ, timestamp });
+                route.ticks.push(Tick {
+                    typ,
+                    timestamp,
+                    styles,
+                    note,
+                    climber: model.persisted.settings.active_climber.clone(),
+                });
+            }
+        }
+
+        Msg::ToggleTickStyle(route_id, style) => {
+            if let Some(tick) = model
+                .persisted
+                .routes
+                .get_mut(&route_id)
+                .and_then(|route| route.ticks.last_mut())
+            {
+                if let Some(pos) = tick.styles.iter().position(|s| *s == style) {
+                    tick.styles.remove(pos);
+                } else {
+                    tick.styles.push(style);
+                }
+            }
+        }
+
+        Msg::TickNoteChanged(route_id, index, note) => {
+            if let Some(tick) = model
+                .persisted
+                .routes
+                .get_mut(&route_id)
+                .and_then(|route| route.ticks.get_mut(index))
+            {
+                tick.note = note;
             }
         }
 
         Msg::ChooseColor(color) => {
             model.data.chosen_color = color;
+            model.data.duplicate_prompt = None;
         }
 
         Msg::ChooseSection(section) => {
             model.data.chosen_section = section;
+            model.data.duplicate_prompt = None;
         }
 
         Msg::ChooseGrade(grade) => {
             model.data.chosen_grade = grade;
+            model.data.duplicate_prompt = None;
+        }
+
+        Msg::ToggleStyle(style) => {
+            let styles = &mut model.data.chosen_styles;
+
+            if let Some(pos) = styles.iter().position(|s| *s == style) {
+                styles.remove(pos);
+            } else {
+                styles.push(style);
+            }
+        }
+
+        Msg::ChooseRating(rating) => {
+            model.data.chosen_rating = if model.data.chosen_rating == Some(rating) {
+                None
+            } else {
+                Some(rating)
+            };
+        }
+
+        Msg::ChooseGradeOpinion(opinion) => {
+            model.data.chosen_grade_opinion = if model.data.chosen_grade_opinion == Some(opinion) {
+                None
+            } else {
+                Some(opinion)
+            };
+        }
+
+        Msg::ToggleProject => {
+            model.data.chosen_project = !model.data.chosen_project;
+        }
+
+        Msg::SetOnChanged(date) => {
+            model.data.chosen_set_on = date;
+        }
+
+        Msg::ExpectedStripChanged(date) => {
+            model.data.chosen_expected_strip = date;
         }
 
         Msg::OpenModal() => {
             model.data.editing_route = None;
+            model.data.duplicate_prompt = None;
             model.data.new_route_title = "".to_string();
+            model.data.new_route_beta = "".to_string();
+            model.data.new_tick_note = "".to_string();
+            model.data.chosen_styles.clear();
+            model.data.chosen_rating = None;
+            model.data.chosen_grade_opinion = None;
+            model.data.chosen_project = false;
+            model.data.chosen_set_on = "".to_string();
+            model.data.chosen_expected_strip = "".to_string();
 
             model.data.modal_open = true;
         }
@@ -326,50 +1115,825 @@ fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
             model.data.modal_open = false;
         }
 
+        Msg::FilterQueryChanged(query) => {
+            model.data.filter.query = query;
+            push_filter_to_url(&model.data.filter);
+        }
+
+        Msg::ToggleFilter(toggle) => {
+            model.data.filter.toggle(toggle);
+            push_filter_to_url(&model.data.filter);
+        }
+
+        Msg::ToggleFilterPanel => {
+            model.data.filter_open = !model.data.filter_open;
+        }
+
+        Msg::ClearFilter => {
+            model.data.filter = Filter::default();
+            push_filter_to_url(&model.data.filter);
+        }
+
+        Msg::ChooseSortOrder(order) => {
+            model.persisted.settings.sort_order = order;
+        }
+
+        Msg::ChooseGrouping(grouping) => {
+            model.persisted.settings.grouping = grouping;
+        }
+
+        Msg::ChooseDayCutover(hour) => {
+            if let Ok(hour) = hour.parse() {
+                model.persisted.settings.day_cutover_hour = hour;
+            }
+        }
+
+        Msg::ChooseWeekStart(weekday) => {
+            model.persisted.settings.week_start = weekday;
+        }
+
+        Msg::ChooseLocale(locale) => {
+            model.persisted.settings.locale = Some(locale);
+        }
+
+        Msg::NewClimberNameChanged(name) => {
+            model.data.new_climber_name = name;
+        }
+
+        Msg::AddClimber => {
+            let name = mem::take(&mut model.data.new_climber_name)
+                .trim()
+                .to_string();
+
+            if !name.is_empty() {
+                let settings = &mut model.persisted.settings;
+                let key = Uuid::new_v4().to_string();
+
+                settings.climbers.insert(key.clone(), Climber { name });
+                settings.active_climber.get_or_insert(key);
+            }
+        }
+
+        Msg::StartRemoveClimber(key) => {
+            model.data.removing_climber = Some(Removal::new(
+                &model.persisted.routes,
+                &model.persisted.settings,
+                key,
+            ));
+        }
+
+        Msg::ChooseClimberHeir(key) => {
+            if let Some(removal) = &mut model.data.removing_climber {
+                removal.heir = Some(key);
+            }
+        }
+
+        Msg::RemoveClimber => {
+            if let Some(removal) = model.data.removing_climber.take() {
+                climber::remove(
+                    &mut model.persisted.routes,
+                    &mut model.persisted.settings,
+                    removal,
+                );
+            }
+        }
+
+        Msg::CancelRemoveClimber => {
+            model.data.removing_climber = None;
+        }
+
+        Msg::ChooseClimber(key) => {
+            model.persisted.settings.active_climber = Some(key);
+        }
+
+        Msg::DrawOutline(key) => {
+            model.data.drawing_outline = key;
+        }
+
+        Msg::AddOutlinePoint(x, y) => {
+            let sections = &mut model.persisted.settings.sections;
+
+            if let Some(section) = model
+                .data
+                .drawing_outline
+                .as_ref()
+                .and_then(|key| sections.get_mut(key))
+            {
+                section.polygon.push((x, y));
+            }
+        }
+
+        Msg::ClearOutline(key) => {
+            if let Some(section) = model.persisted.settings.sections.get_mut(&key) {
+                section.polygon.clear();
+            }
+        }
+
         Msg::ExportData() => {
             if let Ok(json) = serde_json::to_string(&model.persisted) {
                 exportData(json);
+                model.data.health.record_export();
             }
         }
 
+        Msg::ExportArchive() => {
+            // A typo in a passphrase that's only typed once would lock the export for good.
+            if !model.data.passphrase_confirmed() {
+                return;
+            }
+
+            let json = match serde_json::to_string(&model.persisted) {
+                Ok(json) => json,
+                Err(_) => return,
+            };
+            let csv = archive::ticks_csv(&model.persisted.routes);
+            let passphrase = model.data.passphrase.clone();
+            model.data.health.record_export();
+
+            orders.perform_cmd(async move {
+                let photos = JsFuture::from(readAllPhotos())
+                    .await
+                    .map(archive_photos)
+                    .unwrap_or_default();
+
+                let zip = archive::write(&json, STORAGE_KEY, &csv, &photos, Local::now());
+
+                if passphrase.is_empty() {
+                    exportArchive(&zip, "gymticks.zip");
+                    None
+                } else {
+                    match encryption::encrypt(&zip, &passphrase) {
+                        Ok(encrypted) => {
+                            exportArchive(&encrypted, "gymticks.zip.enc");
+                            None
+                        }
+                        Err(error) => Some(Msg::ExportFailed(error)),
+                    }
+                }
+            });
+        }
+
+        Msg::ExportFailed(error) => {
+            model.data.import_error = Some(ArchiveError::Encryption(error));
+        }
+
         Msg::StartImportData() => {
             startImportData();
         }
 
-        Msg::ImportData(json) => {
-            // TODO fail less silently
-            if let Ok(new_data) = serde_json::from_str(&json) {
-                mem::replace(&mut model.persisted, new_data);
+        Msg::ImportData(json) => match serde_json::from_str(&json) {
+            Ok(new_data) => {
+                let (backups, dropped) =
+                    backup::take(&model.persisted, backup::Reason::BeforeImport);
+                model.data.backups = backups;
+                model.data.snapshots_dropped |= dropped;
+                model.persisted = new_data;
+                model.data.import_error = None;
+                model.data.photo_urls.clear();
+                delete_orphaned_photos(model);
             }
+            Err(_) => model.data.import_error = Some(ArchiveError::BadData),
+        },
+
+        Msg::ImportArchive(bytes) => import_archive(model, bytes),
+
+        Msg::PassphraseChanged(passphrase) => {
+            model.data.passphrase = passphrase;
         }
 
-        Msg::NoOp => (),
-    }
+        Msg::PassphraseConfirmationChanged(confirmation) => {
+            model.data.passphrase_confirmation = confirmation;
+        }
 
-    // Save data into LocalStorage. It should be optimized in a real-world application.
-    LocalStorage::insert(STORAGE_KEY, &model.persisted).expect("save persisted to LocalStorage");
-}
+        Msg::RetryImport => {
+            if let Some(bytes) = model.data.pending_import.take() {
+                import_archive(model, bytes);
+            }
+        }
 
-// ------ ------
-//     View
-// ------ ------
+        Msg::RestoreBackup(key) => match backup::load(&key) {
+            Some(restored) => {
+                let (_backups, dropped) =
+                    backup::take(&model.persisted, backup::Reason::BeforeRestore);
+                model.data.snapshots_dropped |= dropped;
+                model.persisted = restored;
+                model.data.backups = backup::list();
+                model.data.photo_urls.clear();
+                delete_orphaned_photos(model);
+                model.data.restore_failed = false;
+
+                let url = Urls::new(&model.base_url).routes();
+                url.go_and_push();
+                orders.notify(subs::UrlChanged(url));
+            }
+            None => model.data.restore_failed = true,
+        },
 
-fn view(model: &Model) -> Vec<Node<Msg>> {
-    let data = &model.data;
-    let persisted = &model.persisted;
+        Msg::DeleteBackup(key) => {
+            model.data.backups = backup::delete(&key);
+        }
 
-    nodes![
-        header![
-            C!["navbar"],
-            section![
-                C!["navbar-section"],
-                a![attrs! {
-                    At::Href => "#"
-                }],
-            ],
-            section![C!["navbar-center"], "gymticks"],
-            section![
-                C!["navbar-section"],
+        Msg::DismissSnapshotsDropped => {
+            model.data.snapshots_dropped = false;
+        }
+
+        Msg::AddTeammates => {
+            orders.perform_cmd(async {
+                let files = JsFuture::from(startAddTeammates()).await.ok()?;
+                let files = js_sys::Array::from(&files)
+                    .iter()
+                    .filter_map(|file| {
+                        let file = js_sys::Array::from(&file);
+
+                        Some((
+                            file.get(0).as_string()?,
+                            js_sys::Uint8Array::new(&file.get(1)).to_vec(),
+                        ))
+                    })
+                    .collect();
+
+                Some(Msg::TeammatesLoaded(files))
+            });
+        }
+
+        Msg::TeammatesLoaded(files) => {
+            let team = &mut model.data.team;
+            model.data.team_error = None;
+
+            for (file_name, bytes) in files {
+                match team::parse_export(&file_name, &bytes, &model.data.team_passphrase) {
+                    // Importing someone again replaces their old export.
+                    Ok(teammate) => match team.iter_mut().find(|t| t.name == teammate.name) {
+                        Some(existing) => *existing = teammate,
+                        None => team.push(teammate),
+                    },
+                    Err(error) => model.data.team_error = Some(error),
+                }
+            }
+
+            save_team(team, orders);
+        }
+
+        Msg::TeamLoaded(json) => {
+            model.data.team = serde_json::from_str(&json).unwrap_or_default();
+        }
+
+        Msg::TeamSaveFailed => {
+            model.data.team_error = Some(TeamError::StorageFull);
+        }
+
+        Msg::TeamPassphraseChanged(passphrase) => {
+            model.data.team_passphrase = passphrase;
+        }
+
+        Msg::RemoveTeammate(index) => {
+            if index < model.data.team.len() {
+                model.data.team.remove(index);
+                model.data.team_error = None;
+                save_team(&model.data.team, orders);
+            }
+        }
+
+        Msg::ChooseChallengeKind(kind) => {
+            model.data.challenge_draft.kind = kind;
+        }
+
+        Msg::ChallengeSectionChanged(section) => {
+            model.data.challenge_draft.section = section;
+        }
+
+        Msg::ChallengeGradeChanged(grade) => {
+            model.data.challenge_draft.grade = grade;
+        }
+
+        Msg::ChallengeCountChanged(count) => {
+            model.data.challenge_draft.count = count;
+        }
+
+        Msg::AddChallenge => {
+            if let Some(challenge) = model
+                .data
+                .challenge_draft
+                .to_challenge(&model.persisted.settings)
+            {
+                model.persisted.challenges.push(challenge);
+            }
+        }
+
+        Msg::RemoveChallenge(index) => {
+            if index < model.persisted.challenges.len() {
+                model.persisted.challenges.remove(index);
+            }
+        }
+
+        Msg::ChooseGoalKind(kind) => {
+            model.data.goal_draft.kind = kind;
+        }
+
+        Msg::GoalGradeChanged(grade) => {
+            model.data.goal_draft.grade = grade;
+        }
+
+        Msg::GoalCountChanged(count) => {
+            model.data.goal_draft.count = count;
+        }
+
+        Msg::ChooseGoalPeriod(period) => {
+            model.data.goal_draft.period = period;
+        }
+
+        Msg::AddGoal => {
+            if let Some(goal) = model.data.goal_draft.to_goal() {
+                model.persisted.goals.push(goal);
+            }
+        }
+
+        Msg::RemoveGoal(index) => {
+            if index < model.persisted.goals.len() {
+                model.persisted.goals.remove(index);
+            }
+        }
+
+        Msg::DismissCompletedGoals => {
+            model.data.completed_goals.clear();
+        }
+
+        Msg::DismissCelebration => {
+            model.data.celebration.clear();
+        }
+
+        Msg::WorkerRegistered(Ok(registration)) => {
+            model.data.health.worker = WorkerStatus::Registered;
+
+            orders.perform_cmd(async move {
+                let worker = JsFuture::from(waitForUpdate(&registration)).await.ok()?;
+                Some(Msg::UpdateAvailable(worker.unchecked_into()))
+            });
+        }
+
+        Msg::WorkerRegistered(Err(error)) => {
+            model.data.health.worker = WorkerStatus::Failed(error);
+        }
+
+        Msg::UpdateAvailable(worker) => {
+            model.data.update = Some(worker);
+        }
+
+        Msg::ReloadForUpdate => {
+            if let Some(worker) = model.data.update.take() {
+                // Reload once the new worker has taken over, so every file comes from its cache.
+                let reload = Closure::once_into_js(|| {
+                    let _ = window().location().reload();
+                });
+                let _ = window()
+                    .navigator()
+                    .service_worker()
+                    .add_event_listener_with_callback("controllerchange", reload.unchecked_ref());
+                let _ = worker.post_message(&JsValue::from_str("skip-waiting"));
+            }
+        }
+
+        Msg::StorageChecked(status) => {
+            model.data.health.storage = Some(status);
+        }
+
+        Msg::RequestPersistentStorage => {
+            orders.perform_cmd(async {
+                let status = JsFuture::from(checkStorage(true)).await.ok()?;
+                Some(Msg::StorageChecked(StorageStatus::from_js(&status)))
+            });
+        }
+
+        Msg::MergeDuplicates(route_ids) => {
+            if let Some((keep, others)) = route_ids.split_first() {
+                model.data.undo = Some(merge::merge_all(
+                    &mut model.persisted.routes,
+                    *keep,
+                    others,
+                    false,
+                ));
+            }
+        }
+
+        Msg::ToggleRouteTools => {
+            model.data.route_tools.open = !model.data.route_tools.open;
+        }
+
+        Msg::ChooseMergeRoute(route_id) => {
+            model.data.route_tools.merge_with = route_id;
+        }
+
+        Msg::ChooseMergeDetails(details_from_other) => {
+            model.data.route_tools.details_from_other = details_from_other;
+        }
+
+        Msg::MergeRoute(route_id) => {
+            let tools = mem::take(&mut model.data.route_tools);
+
+            if let Some(other) = tools.merge_with {
+                model.data.undo = Some(merge::merge_all(
+                    &mut model.persisted.routes,
+                    route_id,
+                    &[other],
+                    tools.details_from_other,
+                ));
+            }
+        }
+
+        Msg::ToggleSplitTick(index) => {
+            let split_ticks = &mut model.data.route_tools.split_ticks;
+
+            if let Some(position) = split_ticks.iter().position(|i| *i == index) {
+                split_ticks.remove(position);
+            } else {
+                split_ticks.push(index);
+            }
+        }
+
+        Msg::SplitRoute(route_id) => {
+            let tools = mem::take(&mut model.data.route_tools);
+
+            if let Some((_new_id, undo)) =
+                merge::split(&mut model.persisted.routes, route_id, &tools.split_ticks)
+            {
+                model.data.undo = Some(undo);
+            }
+        }
+
+        Msg::Undo => {
+            if let Some(undo) = model.data.undo.take() {
+                undo.apply(&mut model.persisted.routes);
+            }
+        }
+
+        Msg::DismissUndo => {
+            if model.data.undo.take().is_some() {
+                delete_orphaned_photos(model);
+            }
+        }
+
+        Msg::QuickChooseSection(section) => {
+            model.data.quick_log.section = Some(section);
+            model.data.quick_log.route = None;
+        }
+
+        Msg::QuickChooseColor(color) => {
+            model.data.quick_log.color = Some(color);
+            model.data.quick_log.route = None;
+        }
+
+        Msg::QuickChooseGrade(grade) => {
+            model.data.quick_log.grade = Some(grade);
+            model.data.quick_log.route = None;
+        }
+
+        Msg::QuickChooseRoute(route_id) => {
+            model.data.quick_log.route = Some(route_id);
+        }
+
+        Msg::QuickTick(typ) => {
+            let quick_log = &mut model.data.quick_log;
+
+            if let (Some(section), Some(color), Some(grade)) =
+                (&quick_log.section, &quick_log.color, &quick_log.grade)
+            {
+                let route_id = match quick_log.target(&model.persisted.routes) {
+                    Some(route_id) => route_id,
+                    None => {
+                        let route_id = RouteId::new_v4();
+
+                        model.persisted.routes.insert(
+                            route_id,
+                            Route {
+                                title: String::new(),
+                                completed: false,
+                                ticks: Vec::new(),
+                                color: color.clone(),
+                                section: section.clone(),
+                                grade: grade.clone(),
+                                retired: false,
+                                beta: String::new(),
+                                rating: None,
+                                grade_opinion: None,
+                                project: false,
+                                created_at: Utc::now().timestamp(),
+                                set_on: None,
+                                expected_strip: None,
+                                photo: None,
+                            },
+                        );
+
+                        route_id
+                    }
+                };
+
+                quick_log.route = Some(route_id);
+                quick_log.last = Some((route_id, typ.clone()));
+
+                orders.send_msg(Msg::AddTickToRoute(route_id, typ, vec![], String::new()));
+            }
+        }
+
+        Msg::KeyPressed(event) => {
+            if let Some(shortcut) = Shortcut::from_event(&event) {
+                if run_shortcut(model, shortcut, orders) {
+                    event.prevent_default();
+                }
+            }
+        }
+
+        Msg::ToggleShortcutHelp => {
+            model.data.shortcut_help = !model.data.shortcut_help;
+        }
+
+        Msg::NoOp => (),
+    }
+
+    // Routes merged away keep their photos for as long as the merge can be undone.
+    if dropped_undo {
+        delete_orphaned_photos(model);
+    }
+
+    // Goals only move when ticks or routes do, but a new goal may be reached already.
+    if changes_routes || adds_goal {
+        model
+            .data
+            .completed_goals
+            .extend(goal::check(&mut model.persisted));
+    }
+
+    // Achievements come from ticks, and ticks logged before any climbers were added belong to
+    // whoever is first.
+    if changes_routes || changes_climbers {
+        let unlocked = achievement::record(&mut model.persisted);
+        if ticked {
+            model.data.celebration = unlocked;
+        }
+    }
+
+    // Save data into LocalStorage. It should be optimized in a real-world application.
+    match backup::save(&model.persisted, &mut model.data.backups) {
+        SaveOutcome::Saved => model.data.save_failed = false,
+        SaveOutcome::DroppedSnapshots => {
+            model.data.save_failed = false;
+            model.data.snapshots_dropped = true;
+        }
+        SaveOutcome::Failed => model.data.save_failed = true,
+    }
+}
+
+/// Takes the styles and note chosen in the modal for a new tick, dropping styles that don't
+/// apply to the chosen grade.
+fn take_chosen_tick(model: &mut Model) -> (Vec<String>, String) {
+    let settings = &model.persisted.settings;
+    let styles = mem::take(&mut model.data.chosen_styles)
+        .into_iter()
+        .filter(|style| {
+            settings
+                .tick_styles_for_grade(&model.data.chosen_grade)
+                .any(|(key, _style)| key == style)
+        })
+        .collect();
+
+    let note = mem::take(&mut model.data.new_tick_note);
+
+    (styles, note)
+}
+
+/// Runs a keyboard shortcut, returning whether it did anything here.
+fn run_shortcut(model: &mut Model, shortcut: Shortcut, orders: &mut impl Orders<Msg>) -> bool {
+    let data = &mut model.data;
+
+    if let Shortcut::Close = shortcut {
+        if data.shortcut_help {
+            data.shortcut_help = false;
+        } else if data.modal_open {
+            data.modal_open = false;
+        } else if data.focused_route.take().is_none() {
+            return false;
+        }
+        return true;
+    }
+
+    if let Shortcut::Help = shortcut {
+        data.shortcut_help = !data.shortcut_help;
+        return true;
+    }
+
+    // Everything else would act behind an open dialog.
+    if data.shortcut_help || data.modal_open {
+        return false;
+    }
+
+    // Sending, attempting and editing act on the open route or the focused one in the list.
+    let target = match model.page {
+        Page::Route(route_id) => Some(route_id),
+        Page::Routes => data.focused_route,
+        _ => None,
+    };
+
+    match shortcut {
+        Shortcut::NewRoute => {
+            orders.send_msg(Msg::OpenModal());
+        }
+        Shortcut::Search => {
+            if !matches!(model.page, Page::Routes) {
+                let url = Urls::new(&model.base_url)
+                    .routes()
+                    .set_search(data.filter.to_url_search());
+                url.go_and_push();
+                orders.notify(subs::UrlChanged(url));
+            }
+            orders.after_next_render(|_| shortcuts::focus("#search"));
+        }
+        Shortcut::Next | Shortcut::Previous => {
+            if !matches!(model.page, Page::Routes) {
+                return false;
+            }
+
+            let route_ids = visible_route_ids(
+                &model.persisted.routes,
+                &model.persisted.settings,
+                &data.filter,
+            );
+            let position = data
+                .focused_route
+                .and_then(|focused| route_ids.iter().position(|route_id| *route_id == focused));
+
+            let next = match (shortcut, position) {
+                (Shortcut::Next, Some(position)) => route_ids.get(position + 1),
+                (Shortcut::Previous, Some(position)) => position
+                    .checked_sub(1)
+                    .and_then(|position| route_ids.get(position)),
+                (Shortcut::Previous, None) => route_ids.last(),
+                _ => route_ids.first(),
+            };
+
+            if let Some(next) = next {
+                data.focused_route = Some(*next);
+                orders.after_next_render(|_| shortcuts::focus(".route-list li.focused"));
+            }
+        }
+        Shortcut::Send | Shortcut::Attempt => match target {
+            Some(route_id) => {
+                let tick_type = if let Shortcut::Send = shortcut {
+                    TickType::Ascent
+                } else {
+                    TickType::Attempt
+                };
+                orders.send_msg(Msg::AddTickToRoute(
+                    route_id,
+                    tick_type,
+                    vec![],
+                    String::new(),
+                ));
+            }
+            None => return false,
+        },
+        Shortcut::Edit => match target {
+            Some(route_id) => {
+                orders.send_msg(Msg::StartRouteEdit(route_id));
+            }
+            None => return false,
+        },
+        Shortcut::Close | Shortcut::Help => (),
+    }
+
+    true
+}
+
+/// Imports an archive, decrypting it first if needed. Encrypted archives are held on to
+/// when the passphrase is missing or wrong so the import can be retried.
+fn import_archive(model: &mut Model, bytes: Vec<u8>) {
+    let bytes = if encryption::is_encrypted(&bytes) {
+        match encryption::decrypt(&bytes, &model.data.passphrase) {
+            Ok(decrypted) => decrypted,
+            Err(error) => {
+                model.data.pending_import = Some(bytes);
+                model.data.import_error = Some(ArchiveError::Encryption(error));
+                return;
+            }
+        }
+    } else {
+        bytes
+    };
+
+    model.data.pending_import = None;
+
+    let contents = archive::read(&bytes).and_then(|contents| {
+        serde_json::from_str(&contents.data_json)
+            .map(|new_data| (new_data, contents.photos))
+            .map_err(|_| ArchiveError::BadData)
+    });
+
+    match contents {
+        Ok((new_data, photos)) => {
+            let (backups, dropped) = backup::take(&model.persisted, backup::Reason::BeforeImport);
+            model.data.backups = backups;
+            model.data.snapshots_dropped |= dropped;
+            model.persisted = new_data;
+            model.data.import_error = None;
+            model.data.photo_urls.clear();
+            delete_orphaned_photos(model);
+            for photo in photos {
+                storePhoto(photo.route_id, &photo.bytes, photo.mime);
+            }
+        }
+        Err(error) => model.data.import_error = Some(error),
+    }
+}
+
+/// Deletes the photos of routes that are gone or have no photo anymore, which would otherwise
+/// linger in IndexedDB after imports, restores and merges.
+fn delete_orphaned_photos(model: &mut Model) {
+    let routes = &model.persisted.routes;
+
+    model
+        .data
+        .photo_urls
+        .retain(|route_id, _url| routes.contains_key(route_id));
+    deletePhotosExcept(
+        routes
+            .iter()
+            .filter(|(_route_id, route)| route.photo.is_some())
+            .map(|(route_id, _route)| JsValue::from(route_id.to_string()))
+            .collect(),
+    );
+}
+
+/// Saves teammates' exports to IndexedDB, which has room for far more of them than
+/// LocalStorage.
+fn save_team(team: &[Teammate], orders: &mut impl Orders<Msg>) {
+    if let Ok(json) = serde_json::to_string(team) {
+        orders.perform_cmd(async move {
+            let saved = JsFuture::from(saveTeam(json)).await;
+            IF!(saved.is_err() => Msg::TeamSaveFailed)
+        });
+    }
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, DATE_FORMAT).ok()
+}
+
+fn format_date(date: Option<NaiveDate>) -> String {
+    date.map_or_else(String::new, |date| date.format(DATE_FORMAT).to_string())
+}
+
+fn push_filter_to_url(filter: &Filter) {
+    Url::current()
+        .set_search(filter.to_url_search())
+        .go_and_replace();
+}
+
+/// Loads the photo of the route being viewed from IndexedDB, if it has one that isn't
+/// loaded yet.
+fn load_photo(model: &Model, orders: &mut impl Orders<Msg>) {
+    let route_id = match model.page {
+        Page::Route(route_id) => route_id,
+        _ => return,
+    };
+
+    let has_photo = model
+        .persisted
+        .routes
+        .get(&route_id)
+        .is_some_and(|route| route.photo.is_some());
+
+    if has_photo && !model.data.photo_urls.contains_key(&route_id) {
+        orders.perform_cmd(async move {
+            let url = JsFuture::from(loadPhoto(route_id.to_string()))
+                .await
+                .ok()?
+                .as_string()?;
+
+            Some(Msg::PhotoLoaded(route_id, url))
+        });
+    }
+}
+
+// ------ ------
+//     View
+// ------ ------
+
+fn view(model: &Model) -> Vec<Node<Msg>> {
+    let data = &model.data;
+    let persisted = &model.persisted;
+    let base_url = &model.base_url;
+    let locale = persisted.settings.locale();
+
+    nodes![
+        header![
+            C!["navbar"],
+            section![
+                C!["navbar-section"],
+                a![attrs! {
+                    At::Href => Urls::new(base_url).routes()
+                }],
+            ],
+            section![C!["navbar-center"], "gymticks"],
+            section![
+                C!["navbar-section"],
                 button![
                     C!["btn btn-primary"],
                     ev(Ev::Click, move |_| Msg::OpenModal()),
@@ -377,44 +1941,203 @@ fn view(model: &Model) -> Vec<Node<Msg>> {
                 ]
             ]
         ],
-        if persisted.routes.is_empty() {
-            vec![]
-        } else {
-            vec![div![
-                C!["container grid-sm"],
-                view_main(&persisted.routes),
-                view_aggregate(&persisted.routes),
-            ]]
+        div![
+            C!["container grid-sm"],
+            view_update_prompt(data.update.is_some(), locale),
+            backup::view_save_failed(data.save_failed, locale),
+            backup::view_snapshots_dropped(data.snapshots_dropped, locale),
+            view_nav(&model.page, base_url, &data.filter, locale),
+            climber::view_climber_switcher(&persisted.settings),
+            merge::view_undo(data.undo.as_ref(), locale),
+            goal::view_completed(&data.completed_goals, locale),
+            achievement::view_celebration(
+                &data.celebration,
+                &persisted.settings,
+                Urls::new(base_url).trophies(),
+            ),
+            view_page(model),
+        ],
+        view_footer(locale),
+        view_pwa_ad(data.pwa_ad, locale),
+        view_modal(data, persisted),
+        shortcuts::view_help(data.shortcut_help, locale),
+    ]
+}
+
+fn view_nav(page: &Page, base_url: &Url, filter: &Filter, locale: Locale) -> Node<Msg> {
+    let tab = |label: Text, url: Url, active: bool| {
+        li![
+            C!["tab-item", IF!(active => "active")],
+            a![attrs! { At::Href => url }, locale.t(label)]
+        ]
+    };
+
+    ul![
+        C!["tab", "tab-block", "page-nav"],
+        tab(
+            Text::Routes,
+            Urls::new(base_url)
+                .routes()
+                .set_search(filter.to_url_search()),
+            matches!(page, Page::Routes | Page::Route(_))
+        ),
+        tab(
+            Text::QuickLog,
+            Urls::new(base_url).quick_log(),
+            matches!(page, Page::QuickLog)
+        ),
+        tab(
+            Text::Stats,
+            Urls::new(base_url).stats(),
+            matches!(page, Page::Stats | Page::Team | Page::Trophies)
+        ),
+        tab(
+            Text::Retired,
+            Urls::new(base_url).retired(),
+            matches!(page, Page::Retired)
+        ),
+        tab(
+            Text::Data,
+            Urls::new(base_url).data(),
+            matches!(page, Page::Data | Page::Backups | Page::Duplicates)
+        ),
+        tab(
+            Text::Settings,
+            Urls::new(base_url).settings(),
+            matches!(page, Page::Settings)
+        ),
+    ]
+}
+
+fn view_page(model: &Model) -> Vec<Node<Msg>> {
+    let data = &model.data;
+    let persisted = &model.persisted;
+    let base_url = &model.base_url;
+    let locale = persisted.settings.locale();
+
+    match &model.page {
+        Page::Routes if persisted.routes.is_empty() => vec![],
+        Page::Routes => vec![
+            view_stripping_soon(&persisted.routes, &persisted.settings, base_url),
+            view_filter_bar(&data.filter, data.filter_open, &persisted.settings),
+            view_main(
+                &persisted.routes,
+                &persisted.settings,
+                &data.filter,
+                data.focused_route,
+                base_url,
+            ),
+        ],
+        Page::Route(route_id) => match persisted.routes.get(route_id) {
+            Some(route) => vec![view_route_detail(
+                route_id,
+                route,
+                &persisted.routes,
+                &persisted.settings,
+                data.photo_urls.get(route_id),
+                &data.route_tools,
+            )],
+            None => vec![view_not_found(locale)],
         },
-        view_footer(),
-        view_pwa_ad(data.pwa_ad),
-        view_modal(
-            &data.modal_open,
-            &data.new_route_title,
-            &data.editing_route,
-            &data.chosen_color,
-            &data.chosen_section,
-            &data.chosen_grade,
-            &persisted.settings.colors,
-            &persisted.settings.sections,
-            &persisted.settings.grades,
+        Page::QuickLog => vec![quick_log::view_quick_log(
+            &data.quick_log,
+            &persisted.routes,
+            &persisted.settings,
+        )],
+        Page::Stats => vec![
+            heatmap::view_heatmap(&persisted.routes, &persisted.settings),
+            charts::view_progress(&persisted.routes, &persisted.settings),
+            gym_map::view_wall_heatmap(&persisted.routes, &persisted.settings),
+            view_aggregate(&persisted.routes, &persisted.settings),
+            goal::view_goals(
+                &persisted.goals,
+                &persisted.routes,
+                &persisted.settings,
+                &data.goal_draft,
+            ),
+            achievement::view_recent(persisted, Urls::new(base_url).trophies()),
+            team::view_team_link(Urls::new(base_url).team(), data.team.len(), locale),
+        ],
+        Page::Trophies => vec![achievement::view_trophies(persisted)],
+        Page::Team => team::view_team(
+            persisted,
+            &data.team,
+            &data.challenge_draft,
+            &data.team_passphrase,
+            data.team_error.as_ref(),
         ),
+        Page::Settings => vec![
+            view_settings(
+                &persisted.settings,
+                &data.new_climber_name,
+                data.removing_climber.as_ref(),
+            ),
+            gym_map::view_map_editor(
+                &persisted.settings.sections,
+                data.drawing_outline.as_deref(),
+                locale,
+            ),
+            health::view_health(&data.health, Urls::new(base_url).data(), locale),
+        ],
+        Page::Retired => vec![view_retired(&persisted.routes, base_url, locale)],
+        Page::Data => vec![view_data(data, base_url, locale)],
+        Page::Backups => vec![backup::view_backups(
+            &data.backups,
+            data.restore_failed,
+            locale,
+        )],
+        Page::Duplicates => vec![merge::view_duplicates(
+            &persisted.routes,
+            &persisted.settings,
+            base_url,
+        )],
+        Page::Day(date) => vec![view_day(
+            &persisted.routes,
+            &persisted.settings,
+            *date,
+            base_url,
+        )],
+        Page::NotFound => vec![view_not_found(locale)],
+    }
+}
+
+fn view_not_found(locale: Locale) -> Node<Msg> {
+    div![
+        C!["main card"],
+        div![C!["card-body", "text-gray"], locale.t(Text::NothingHere)]
     ]
 }
 
 // ------ header ------
 
-fn view_modal(
-    modal_open: &bool,
-    new_route_title: &str,
-    editing_route: &Option<RouteId>,
-    chosen_color: &str,
-    chosen_section: &str,
-    chosen_grade: &str,
-    colors: &IndexMap<String, Color>,
-    sections: &IndexMap<String, Section>,
-    grades: &IndexMap<String, Grade>,
-) -> Node<Msg> {
+fn view_modal(data: &Data, persisted: &PersistedData) -> Node<Msg> {
+    let Data {
+        modal_open,
+        new_route_title,
+        new_route_beta,
+        new_tick_note,
+        editing_route,
+        chosen_color,
+        chosen_section,
+        chosen_grade,
+        chosen_styles,
+        chosen_rating,
+        chosen_grade_opinion,
+        chosen_project,
+        chosen_set_on,
+        chosen_expected_strip,
+        duplicate_prompt,
+        ..
+    } = data;
+
+    let settings = &persisted.settings;
+    let locale = settings.locale();
+    let colors = &settings.colors;
+    let sections = &settings.sections;
+    let grades = &settings.grades;
+
+    let editing = editing_route.and_then(|id| persisted.routes.get(&id).map(|route| (id, route)));
+
     div![
         C!["modal", IF!(*modal_open => "active")],
         a![
@@ -437,7 +2160,7 @@ fn view_modal(
                         input![
                             C!["form-input"],
                             attrs! {
-                                At::Placeholder => "Description of route";
+                                At::Placeholder => locale.t(Text::DescriptionOfRoute);
                                 At::AutoFocus => true.as_at_value();
                                 At::Value => new_route_title;
                             },
@@ -469,10 +2192,16 @@ fn view_modal(
                             })
                             .collect::<Vec<Node<Msg>>>()
                     ],
+                    IF!(gym_map::has_map(sections) => gym_map::view_section_picker(
+                        sections,
+                        chosen_section,
+                        Msg::ChooseSection,
+                    )),
                     div![
                         C!["section-chooser",],
                         sections
                             .iter()
+                            .filter(|(_k, v)| !v.on_map())
                             .group_by(|(_k, v)| v.group.to_owned())
                             .into_iter()
                             .map(|(_key, group)| {
@@ -527,6 +2256,66 @@ fn view_modal(
                             })
                             .collect::<Vec<Node<Msg>>>()
                     ],
+                    IF!(editing_route.is_none() => view_style_chooser(
+                        settings.tick_styles_for_grade(chosen_grade),
+                        chosen_styles,
+                        Msg::ToggleStyle,
+                    )),
+                    IF!(editing_route.is_none() => input![
+                        C!["form-input", "tick-note-input"],
+                        attrs! {
+                            At::Placeholder => locale.t(Text::NoteForThisTick);
+                            At::Value => new_tick_note;
+                        },
+                        input_ev(Ev::Input, Msg::NewTickNoteChanged),
+                    ]),
+                    view_route_opinions(
+                        *chosen_rating,
+                        *chosen_grade_opinion,
+                        *chosen_project,
+                        locale
+                    ),
+                    textarea![
+                        C!["form-input", "beta-input"],
+                        attrs! {
+                            At::Placeholder => locale.t(Text::Beta);
+                            At::Rows => 2;
+                            At::Value => new_route_beta;
+                        },
+                        input_ev(Ev::Input, Msg::NewRouteBetaChanged),
+                    ],
+                    div![
+                        C!["route-dates"],
+                        label![
+                            C!["form-label"],
+                            locale.t(Text::SetOn),
+                            input![
+                                C!["form-input", "input-sm"],
+                                attrs! {
+                                    At::Type => "date";
+                                    At::Value => chosen_set_on;
+                                },
+                                input_ev(Ev::Input, Msg::SetOnChanged),
+                            ],
+                        ],
+                        label![
+                            C!["form-label"],
+                            locale.t(Text::ExpectedStrip),
+                            input![
+                                C!["form-input", "input-sm"],
+                                attrs! {
+                                    At::Type => "date";
+                                    At::Value => chosen_expected_strip;
+                                },
+                                input_ev(Ev::Input, Msg::ExpectedStripChanged),
+                            ],
+                        ],
+                    ],
+                    editing.map(|(route_id, route)| view_tick_notes(
+                        route_id,
+                        &route.ticks,
+                        locale
+                    )),
                 ],
                 if editing_route.is_some() {
                     div![
@@ -534,14 +2323,16 @@ fn view_modal(
                         button![
                             C!["btn btn-primary new-route-button"],
                             ev(Ev::Click, |_| Msg::SaveEditingRoute),
-                            "Save Changes"
+                            locale.t(Text::SaveChanges)
                         ],
                         button![
                             C!["btn btn-error new-route-button"],
                             ev(Ev::Click, |_| Msg::RetireEditingRoute),
-                            "Retire Route"
+                            locale.t(Text::RetireRoute)
                         ],
                     ]
+                } else if let Some((route_id, tick_type)) = duplicate_prompt {
+                    view_duplicate_prompt(persisted.routes.get(route_id), tick_type.clone(), locale)
                 } else {
                     div![
                         C!["modal-buttons"],
@@ -550,44 +2341,444 @@ fn view_modal(
                             ev(Ev::Click, move |_| Msg::CreateNewRoute(Some(
                                 TickType::Ascent
                             ))),
-                            "SND"
+                            locale.t(Text::Send)
                         ],
                         button![
                             C!["btn new-route-button"],
                             ev(Ev::Click, move |_| Msg::CreateNewRoute(Some(
                                 TickType::Attempt
                             ))),
-                            "ATT"
+                            locale.t(Text::Attempt)
                         ],
                         button![
                             C!["btn btn-secondary new-route-button"],
                             ev(Ev::Click, move |_| Msg::CreateNewRoute(None)),
-                            "Add Without Tick"
+                            locale.t(Text::AddWithoutTick)
                         ],
                     ]
                 }
             ],
-        ]
+        ]
+    ]
+}
+
+fn view_duplicate_prompt(
+    existing: Option<&Route>,
+    tick_type: Option<TickType>,
+    locale: Locale,
+) -> Node<Msg> {
+    div![
+        C!["duplicate-prompt"],
+        div![
+            C!["toast", "toast-warning"],
+            locale.fill(
+                Text::DuplicateRoute,
+                &[&existing.map(Route::name).unwrap_or_default()]
+            )
+        ],
+        div![
+            C!["modal-buttons"],
+            button![
+                C!["btn btn-primary new-route-button"],
+                ev(Ev::Click, |_| Msg::UseExistingRoute),
+                locale.t(Text::UseExistingRoute)
+            ],
+            button![
+                C!["btn new-route-button"],
+                ev(Ev::Click, move |_| Msg::ConfirmNewRoute(tick_type)),
+                locale.t(Text::CreateAnyway)
+            ],
+        ],
+    ]
+}
+
+fn view_route_opinions(
+    chosen_rating: Option<u8>,
+    chosen_grade_opinion: Option<GradeOpinion>,
+    chosen_project: bool,
+    locale: Locale,
+) -> Node<Msg> {
+    div![
+        C!["route-opinions"],
+        div![
+            C!["rating-chooser"],
+            (1..=5)
+                .map(|rating| {
+                    span![
+                        C![IF!(chosen_rating.is_some_and(|r| rating <= r) => "active")],
+                        ev(Ev::Click, move |_| Msg::ChooseRating(rating)),
+                        "\u{2605}"
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>()
+        ],
+        div![
+            C!["style-chooser"],
+            [GradeOpinion::Soft, GradeOpinion::Hard]
+                .iter()
+                .map(|opinion| {
+                    let opinion = *opinion;
+
+                    span![
+                        C![
+                            "chip",
+                            IF!(chosen_grade_opinion == Some(opinion) => "active")
+                        ],
+                        ev(Ev::Click, move |_| Msg::ChooseGradeOpinion(opinion)),
+                        locale.t(opinion.label())
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>(),
+            span![
+                C!["chip", IF!(chosen_project => "active")],
+                ev(Ev::Click, |_| Msg::ToggleProject),
+                locale.t(Text::Project)
+            ],
+        ],
+    ]
+}
+
+fn view_tick_notes(route_id: RouteId, ticks: &[Tick], locale: Locale) -> Node<Msg> {
+    div![
+        C!["tick-notes"],
+        ticks
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, tick)| {
+                div![
+                    C!["tick-note"],
+                    small![format!(
+                        "{} {} {}",
+                        Local
+                            .timestamp_opt(tick.timestamp, 0)
+                            .single()
+                            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_default(),
+                        locale.t(tick.typ.label()),
+                        tick.styles.join(" ")
+                    )],
+                    input![
+                        C!["form-input", "input-sm"],
+                        attrs! {
+                            At::Placeholder => locale.t(Text::Note);
+                            At::Value => tick.note;
+                        },
+                        input_ev(Ev::Input, move |note| Msg::TickNoteChanged(
+                            route_id, index, note
+                        )),
+                    ]
+                ]
+            })
+            .collect::<Vec<Node<Msg>>>()
+    ]
+}
+
+fn view_style_chooser<'a>(
+    styles: impl Iterator<Item = (&'a String, &'a TickStyle)>,
+    chosen_styles: &[String],
+    on_click: impl FnOnce(String) -> Msg + Clone + 'static,
+) -> Node<Msg> {
+    div![
+        C!["style-chooser"],
+        styles
+            .map(|(key, style)| {
+                span![
+                    C!["chip", IF!(chosen_styles.contains(key) => "active")],
+                    ev(Ev::Click, enc!((key, on_click) move |_| on_click(key))),
+                    style.label.as_str()
+                ]
+            })
+            .collect::<Vec<Node<Msg>>>()
+    ]
+}
+
+// ------ main ------
+
+fn view_stripping_soon(
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+    base_url: &Url,
+) -> Node<Msg> {
+    let locale = settings.locale();
+    let today = settings.today();
+    let soon = today + Duration::days(STRIPPING_SOON_DAYS);
+
+    let mut stripping: Vec<(&RouteId, &Route, NaiveDate)> = routes
+        .iter()
+        .filter(|(_k, v)| !v.retired)
+        .filter_map(|(k, v)| {
+            v.expected_strip
+                .filter(|date| *date >= today && *date <= soon)
+                .map(|date| (k, v, date))
+        })
+        .collect();
+
+    if stripping.is_empty() {
+        return empty![];
+    }
+
+    stripping.sort_by_key(|(_k, _v, date)| *date);
+
+    div![
+        C!["main card", "stripping-soon"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::StrippingSoon)]
+        ],
+        ul![
+            C!["route-list"],
+            stripping
+                .into_iter()
+                .map(|(route_id, route, date)| {
+                    let sent = route
+                        .ticks
+                        .iter()
+                        .any(|tick| matches!(tick.typ, TickType::Ascent));
+
+                    li![
+                        C![IF!(sent => "completed"), IF!(route.project => "project")],
+                        div![
+                            C!["view"],
+                            div![
+                                C![route.color.as_str(), "color-flag"],
+                                div![route.section.as_str()],
+                                div![route.grade.as_str()],
+                            ],
+                            label![a![
+                                attrs! {
+                                    At::Href => Urls::new(base_url).route(route_id)
+                                },
+                                route.title.as_str()
+                            ]],
+                            div![
+                                C!["stats"],
+                                div![locale.t(if sent { Text::Sent } else { Text::Unsent })],
+                                div![locale.fill(Text::StripInDays, &[&(date - today).num_days()])],
+                            ],
+                        ]
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>()
+        ]
+    ]
+}
+
+fn view_filter_bar(filter: &Filter, filter_open: bool, settings: &Settings) -> Node<Msg> {
+    let locale = settings.locale();
+
+    div![
+        C!["filter-bar", "card"],
+        div![
+            C!["input-group"],
+            input![
+                C!["form-input"],
+                id!("search"),
+                attrs! {
+                    At::Type => "search";
+                    At::Placeholder => locale.t(Text::SearchRoutes);
+                    At::Value => filter.query;
+                },
+                input_ev(Ev::Input, Msg::FilterQueryChanged),
+            ],
+            button![
+                C!["btn", "input-group-btn", IF!(filter_open => "active")],
+                ev(Ev::Click, |_| Msg::ToggleFilterPanel),
+                i![C!["icon", "icon-menu"]]
+            ],
+            IF!(!filter.is_empty() => button![
+                C!["btn", "input-group-btn"],
+                ev(Ev::Click, |_| Msg::ClearFilter),
+                i![C!["icon", "icon-cross"]]
+            ]),
+        ],
+        IF!(filter_open => div![
+            C!["filter-panel"],
+            div![
+                C!["style-chooser"],
+                settings
+                    .sections
+                    .keys()
+                    .map(|key| view_filter_chip(
+                        key,
+                        filter.sections.contains(key),
+                        FilterToggle::Section(key.clone())
+                    ))
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            div![
+                C!["style-chooser"],
+                settings
+                    .grades
+                    .keys()
+                    .map(|key| view_filter_chip(
+                        key,
+                        filter.grades.contains(key),
+                        FilterToggle::Grade(key.clone())
+                    ))
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            div![
+                C!["style-chooser"],
+                settings
+                    .colors
+                    .iter()
+                    .map(|(key, color)| view_filter_chip(
+                        &color.label,
+                        filter.colors.contains(key),
+                        FilterToggle::Color(key.clone())
+                    ))
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            div![
+                C!["style-chooser"],
+                view_filter_chip(
+                    locale.t(Text::Sent),
+                    filter.sent == Some(true),
+                    FilterToggle::Sent(true)
+                ),
+                view_filter_chip(
+                    locale.t(Text::Unsent),
+                    filter.sent == Some(false),
+                    FilterToggle::Sent(false)
+                ),
+                view_filter_chip(
+                    locale.t(Text::Project),
+                    filter.project,
+                    FilterToggle::Project
+                ),
+                UNTOUCHED_DAYS
+                    .iter()
+                    .map(|days| view_filter_chip(
+                        &locale.fill(Text::UntouchedDays, &[days]),
+                        filter.untouched_days == Some(*days),
+                        FilterToggle::UntouchedDays(*days)
+                    ))
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            view_sort_chooser(settings),
+        ]),
     ]
 }
 
-// ------ main ------
+fn view_sort_chooser(settings: &Settings) -> Vec<Node<Msg>> {
+    let locale = settings.locale();
+
+    vec![
+        div![
+            C!["style-chooser"],
+            span![C!["chip-label"], locale.t(Text::Sort)],
+            SortOrder::ALL
+                .iter()
+                .map(|order| {
+                    let order = *order;
+
+                    span![
+                        C!["chip", IF!(settings.sort_order == order => "active")],
+                        ev(Ev::Click, move |_| Msg::ChooseSortOrder(order)),
+                        locale.t(order.label())
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>()
+        ],
+        div![
+            C!["style-chooser"],
+            span![C!["chip-label"], locale.t(Text::Group)],
+            Grouping::ALL
+                .iter()
+                .map(|grouping| {
+                    let grouping = *grouping;
+
+                    span![
+                        C!["chip", IF!(settings.grouping == grouping => "active")],
+                        ev(Ev::Click, move |_| Msg::ChooseGrouping(grouping)),
+                        locale.t(grouping.label())
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>()
+        ],
+    ]
+}
+
+fn view_filter_chip(label: &str, active: bool, toggle: FilterToggle) -> Node<Msg> {
+    span![
+        C!["chip", IF!(active => "active")],
+        ev(Ev::Click, move |_| Msg::ToggleFilter(toggle)),
+        label
+    ]
+}
+
+/// The routes shown in the route list, in the order they're shown.
+fn visible_route_ids(
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+    filter: &Filter,
+) -> Vec<RouteId> {
+    let now = Utc::now().timestamp();
+
+    sort::sorted_route_ids(
+        routes
+            .iter()
+            .filter(|(_k, v)| !v.retired && filter.matches(v, now)),
+        settings,
+        settings.sort_order,
+        settings.grouping,
+    )
+}
+
+fn view_main(
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+    filter: &Filter,
+    focused_route: Option<RouteId>,
+    base_url: &Url,
+) -> Node<Msg> {
+    let route_ids = visible_route_ids(routes, settings, filter);
 
-fn view_main(routes: &IndexMap<RouteId, Route>) -> Node<Msg> {
-    section![routes
+    let cards = route_ids
         .iter()
-        .filter(|(_k, v)| !v.retired)
-        .group_by(|(_k, v)| v.section.to_owned())
+        .group_by(|route_id| {
+            routes
+                .get(*route_id)
+                .and_then(|route| settings.grouping.key(route))
+        })
         .into_iter()
         .map(|(_k, group)| {
-            let route_ids = group.into_iter().map(|(k, _v)| *k).collect();
+            let route_ids = group.copied().collect();
 
-            div![C!["main card"], div![view_routes(routes, route_ids)]]
+            div![
+                C!["main card"],
+                div![view_routes(
+                    routes,
+                    settings,
+                    route_ids,
+                    focused_route,
+                    base_url
+                )]
+            ]
         })
-        .collect::<Vec<Node<Msg>>>()]
+        .collect::<Vec<Node<Msg>>>();
+
+    if cards.is_empty() {
+        section![div![
+            C!["main card"],
+            div![
+                C!["card-body", "text-gray"],
+                settings.locale().t(Text::NoRoutesMatch)
+            ]
+        ]]
+    } else {
+        section![cards]
+    }
 }
 
-fn view_routes(routes: &IndexMap<RouteId, Route>, route_ids: Vec<RouteId>) -> Node<Msg> {
+fn view_routes(
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+    route_ids: Vec<RouteId>,
+    focused_route: Option<RouteId>,
+    base_url: &Url,
+) -> Node<Msg> {
     let time = Utc::now();
 
     ul![
@@ -596,7 +2787,14 @@ fn view_routes(routes: &IndexMap<RouteId, Route>, route_ids: Vec<RouteId>) -> No
             .iter()
             .filter_map(|route_id| {
                 if let Some(route) = routes.get(route_id) {
-                    Some(view_route(route_id, route, &time))
+                    Some(view_route(
+                        route_id,
+                        route,
+                        settings,
+                        &time,
+                        focused_route == Some(*route_id),
+                        base_url,
+                    ))
                 } else {
                     None
                 }
@@ -605,7 +2803,14 @@ fn view_routes(routes: &IndexMap<RouteId, Route>, route_ids: Vec<RouteId>) -> No
     ]
 }
 
-fn view_route(route_id: &RouteId, route: &Route, time: &DateTime<Utc>) -> Node<Msg> {
+fn view_route(
+    route_id: &RouteId,
+    route: &Route,
+    settings: &Settings,
+    time: &DateTime<Utc>,
+    focused: bool,
+    base_url: &Url,
+) -> Node<Msg> {
     let mut num_ascents = 0;
     let mut num_attempts = 0;
     let mut attempts_to_ascent = 0;
@@ -614,6 +2819,8 @@ fn view_route(route_id: &RouteId, route: &Route, time: &DateTime<Utc>) -> Node<M
     let mut last_attempt = 0;
     let mut _ascent_streak = 0;
 
+    let locale = settings.locale();
+
     // TODO: can we iterate our way out of this mess?
 
     for tick in &route.ticks {
@@ -638,40 +2845,54 @@ fn view_route(route_id: &RouteId, route: &Route, time: &DateTime<Utc>) -> Node<M
     }
 
     let ascent_text = if num_ascents == 0 {
-        String::from("unsent")
+        locale.t(Text::Unsent).to_string()
     } else if attempts_to_ascent == 0 {
-        format!("{} snd (flsh)", num_ascents)
+        locale.fill(Text::SendsFlash, &[&num_ascents])
     } else if attempts_to_ascent > 0 {
-        format!("{} snd ({} att)", num_ascents, attempts_to_ascent)
+        locale.fill(
+            Text::SendsAfterAttempts,
+            &[&num_ascents, &attempts_to_ascent],
+        )
     } else {
         // unreachable?
         String::new()
     };
 
+    let ago = |timestamp: i64| {
+        Utc.timestamp_opt(timestamp, 0)
+            .single()
+            .map(|at| util::time_diff_in_words(at, *time, locale))
+            .unwrap_or_default()
+    };
+
     let att_text = if num_attempts == 0 && num_ascents == 0 {
-        String::from("unattempted")
+        locale.t(Text::Unattempted).to_string()
     } else if num_ascents == 0 {
-        format!(
-            "{} att (att {})",
-            num_attempts,
-            util::time_diff_in_words(Utc.timestamp(last_attempt, 0), *time)
+        locale.fill(
+            Text::AttemptsLastAttempt,
+            &[&num_attempts, &ago(last_attempt)],
         )
     } else if last_ascent >= last_attempt {
-        format!(
-            "{} att (snd {})",
-            attempts_since_ascent,
-            util::time_diff_in_words(Utc.timestamp(last_ascent, 0), *time)
+        locale.fill(
+            Text::AttemptsLastSend,
+            &[&attempts_since_ascent, &ago(last_ascent)],
         )
     } else {
-        format!(
-            "{} att (att {})",
-            attempts_since_ascent,
-            util::time_diff_in_words(Utc.timestamp(last_attempt, 0), *time)
+        locale.fill(
+            Text::AttemptsLastAttempt,
+            &[&attempts_since_ascent, &ago(last_attempt)],
         )
     };
 
     li![
-        C![IF!(num_ascents > 0 => "completed")],
+        C![
+            IF!(num_ascents > 0 => "completed"),
+            IF!(route.project => "project"),
+            IF!(focused => "focused")
+        ],
+        attrs! {
+            At::TabIndex => -1;
+        },
         div![
             C!["view"],
             div![
@@ -687,87 +2908,676 @@ fn view_route(route_id: &RouteId, route: &Route, time: &DateTime<Utc>) -> Node<M
                 C!["tick-button btn btn-primary"],
                 ev(
                     Ev::Click,
-                    enc!((route_id) move |_| Msg::AddTickToRoute(route_id, TickType::Ascent))
+                    enc!((route_id) move |_| Msg::AddTickToRoute(
+                        route_id,
+                        TickType::Ascent,
+                        vec![],
+                        String::new()
+                    ))
                 ),
-                "SND"
+                locale.t(Text::Send)
             ],
             button![
                 C!["tick-button btn"],
                 ev(
                     Ev::Click,
-                    enc!((route_id) move |_| Msg::AddTickToRoute(route_id, TickType::Attempt))
+                    enc!((route_id) move |_| Msg::AddTickToRoute(
+                        route_id,
+                        TickType::Attempt,
+                        vec![],
+                        String::new()
+                    ))
                 ),
-                "ATT"
+                locale.t(Text::Attempt)
             ],
             label![
                 ev(
                     Ev::DblClick,
                     enc!((route_id) move |_| Msg::StartRouteEdit(route_id))
                 ),
-                route.title.as_str()
+                route.title.as_str(),
+                view_route_badges(route, locale),
+                climber::view_senders(route, settings),
             ],
             div![
                 C!["stats"],
                 div![C!["stats-ascents"], ascent_text,],
                 div![C!["stats-attempts"], att_text,],
+                route
+                    .set_timestamp()
+                    .and_then(|set| Utc.timestamp_opt(set, 0).single())
+                    .map(|set| div![
+                        C!["stats-age"],
+                        locale.fill(
+                            Text::SetAgo,
+                            &[&util::time_diff_in_words(set, *time, locale)]
+                        )
+                    ]),
+            ],
+            a![
+                C!["btn", "btn-link", "btn-sm"],
+                attrs! {
+                    At::Href => Urls::new(base_url).route(route_id)
+                },
+                i![C!["icon", "icon-arrow-right"]]
+            ],
+        ],
+        route.ticks.last().map(|tick| {
+            let route_id = *route_id;
+
+            view_style_chooser(
+                settings.tick_styles_for_grade(&route.grade),
+                &tick.styles,
+                move |style| Msg::ToggleTickStyle(route_id, style),
+            )
+        }),
+    ]
+}
+
+fn view_route_badges(route: &Route, locale: Locale) -> Vec<Node<Msg>> {
+    vec![
+        route.rating.map_or(empty![], |rating| {
+            span![C!["route-rating"], "\u{2605}".repeat(rating.into())]
+        }),
+        route.grade_opinion.map_or(empty![], |opinion| {
+            span![
+                C!["label", "label-rounded", "grade-opinion"],
+                locale.t(opinion.label())
+            ]
+        }),
+    ]
+}
+
+fn view_route_detail(
+    route_id: &RouteId,
+    route: &Route,
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+    photo_url: Option<&String>,
+    tools: &RouteTools,
+) -> Node<Msg> {
+    let route_id = *route_id;
+    let locale = settings.locale();
+
+    div![
+        C!["main card", "route-detail"],
+        div![
+            C!["card-header", "description-and-flag"],
+            div![
+                C![route.color.as_str(), "color-flag"],
+                div![route.section.as_str()],
+                div![route.grade.as_str()],
+            ],
+            div![
+                C!["card-title", "h5"],
+                route.title.as_str(),
+                view_route_badges(route, locale),
+                climber::view_senders(route, settings),
+                IF!(route.retired => span![
+                    C!["label", "label-rounded"],
+                    locale.t(Text::RetiredLabel)
+                ]),
+            ],
+        ],
+        div![
+            C!["card-body"],
+            route
+                .photo
+                .as_ref()
+                .map(|photo| photo::view_photo(route_id, photo, photo_url)),
+            IF!(!route.beta.is_empty() => p![C!["route-beta"], route.beta.as_str()]),
+            route.ticks.last().map(|tick| {
+                view_style_chooser(
+                    settings.tick_styles_for_grade(&route.grade),
+                    &tick.styles,
+                    move |style| Msg::ToggleTickStyle(route_id, style),
+                )
+            }),
+            view_tick_notes(route_id, &route.ticks, locale),
+            IF!(tools.open => merge::view_route_tools(route_id, route, routes, tools, locale)),
+        ],
+        div![
+            C!["card-footer", "modal-buttons"],
+            button![
+                C!["btn", "btn-primary"],
+                ev(Ev::Click, move |_| Msg::AddTickToRoute(
+                    route_id,
+                    TickType::Ascent,
+                    vec![],
+                    String::new()
+                )),
+                locale.t(Text::Send)
+            ],
+            button![
+                C!["btn"],
+                ev(Ev::Click, move |_| Msg::AddTickToRoute(
+                    route_id,
+                    TickType::Attempt,
+                    vec![],
+                    String::new()
+                )),
+                locale.t(Text::Attempt)
+            ],
+            button![
+                C!["btn", "btn-secondary"],
+                ev(Ev::Click, move |_| Msg::StartRouteEdit(route_id)),
+                locale.t(Text::Edit)
+            ],
+            button![
+                C!["btn", "btn-secondary"],
+                ev(Ev::Click, move |_| Msg::AddPhoto(route_id)),
+                locale.t(if route.photo.is_some() {
+                    Text::ReplacePhoto
+                } else {
+                    Text::AddPhoto
+                })
+            ],
+            IF!(route.photo.is_some() => button![
+                C!["btn", "btn-error"],
+                ev(Ev::Click, move |_| Msg::RemovePhoto(route_id)),
+                locale.t(Text::RemovePhoto)
+            ]),
+            button![
+                C!["btn", "btn-secondary", IF!(tools.open => "active")],
+                ev(Ev::Click, |_| Msg::ToggleRouteTools),
+                locale.t(Text::MergeOrSplit)
+            ],
+        ],
+    ]
+}
+
+fn view_retired(routes: &IndexMap<RouteId, Route>, base_url: &Url, locale: Locale) -> Node<Msg> {
+    let retired = routes
+        .iter()
+        .filter(|(_k, v)| v.retired)
+        .map(|(route_id, route)| {
+            let route_id = *route_id;
+
+            li![div![
+                C!["view"],
+                div![
+                    C![route.color.as_str(), "color-flag"],
+                    div![route.section.as_str()],
+                    div![route.grade.as_str()],
+                ],
+                label![a![
+                    attrs! {
+                        At::Href => Urls::new(base_url).route(&route_id)
+                    },
+                    route.title.as_str()
+                ]],
+                button![
+                    C!["tick-button btn btn-sm"],
+                    ev(Ev::Click, move |_| Msg::UnretireRoute(route_id)),
+                    locale.t(Text::Unretire)
+                ],
+            ]]
+        })
+        .collect::<Vec<Node<Msg>>>();
+
+    div![
+        C!["main card"],
+        if retired.is_empty() {
+            div![
+                C!["card-body", "text-gray"],
+                locale.t(Text::NoRetiredRoutes)
+            ]
+        } else {
+            ul![C!["route-list"], retired]
+        }
+    ]
+}
+
+fn view_settings(
+    settings: &Settings,
+    new_climber_name: &str,
+    removing_climber: Option<&Removal>,
+) -> Node<Msg> {
+    let locale = settings.locale();
+
+    div![
+        C!["main card"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Settings)]
+        ],
+        div![
+            C!["card-body"],
+            view_sort_chooser(settings),
+            div![
+                C!["form-group"],
+                label![C!["form-label"], locale.t(Text::DayCutover)],
+                select![
+                    C!["form-select"],
+                    (0..12)
+                        .map(|hour| {
+                            option![
+                                attrs! {
+                                    At::Value => hour;
+                                    At::Selected => (settings.day_cutover_hour == hour).as_at_value();
+                                },
+                                format!("{:02}:00", hour)
+                            ]
+                        })
+                        .collect::<Vec<Node<Msg>>>(),
+                    input_ev(Ev::Change, Msg::ChooseDayCutover),
+                ],
+            ],
+            div![
+                C!["style-chooser"],
+                span![C!["chip-label"], locale.t(Text::WeekStarts)],
+                [Weekday::Mon, Weekday::Sat, Weekday::Sun]
+                    .iter()
+                    .map(|weekday| {
+                        let weekday = *weekday;
+
+                        span![
+                            C!["chip", IF!(settings.week_start == weekday => "active")],
+                            ev(Ev::Click, move |_| Msg::ChooseWeekStart(weekday)),
+                            locale.t(Text::Weekday(weekday))
+                        ]
+                    })
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            div![
+                C!["style-chooser"],
+                span![C!["chip-label"], locale.t(Text::Language)],
+                Locale::ALL
+                    .iter()
+                    .map(|choice| {
+                        let choice = *choice;
+
+                        span![
+                            C!["chip", IF!(locale == choice => "active")],
+                            ev(Ev::Click, move |_| Msg::ChooseLocale(choice)),
+                            choice.label()
+                        ]
+                    })
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            view_climber_settings(settings, new_climber_name, removing_climber),
+        ],
+    ]
+}
+
+fn view_climber_settings(
+    settings: &Settings,
+    new_climber_name: &str,
+    removing_climber: Option<&Removal>,
+) -> Node<Msg> {
+    let locale = settings.locale();
+
+    div![
+        C!["form-group", "climber-settings"],
+        label![C!["form-label"], locale.t(Text::Climbers)],
+        div![
+            C!["style-chooser"],
+            settings
+                .climbers
+                .iter()
+                .map(|(key, climber)| {
+                    let key = key.clone();
+
+                    span![
+                        C!["chip"],
+                        climber.name.as_str(),
+                        a![
+                            C!["btn", "btn-clear"],
+                            attrs! {
+                                At::AriaLabel => locale.t(Text::Delete);
+                            },
+                            ev(Ev::Click, move |_| Msg::StartRemoveClimber(key)),
+                        ]
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>()
+        ],
+        removing_climber.map(|removal| climber::view_removal(removal, settings)),
+        div![
+            C!["input-group"],
+            input![
+                C!["form-input"],
+                attrs! {
+                    At::Placeholder => locale.t(Text::ClimberName);
+                    At::Value => new_climber_name;
+                },
+                keyboard_ev(Ev::KeyDown, |keyboard_event| {
+                    if keyboard_event.key_code() == ENTER_KEY {
+                        Msg::AddClimber
+                    } else {
+                        Msg::NoOp
+                    }
+                }),
+                input_ev(Ev::Input, Msg::NewClimberNameChanged),
+            ],
+            button![
+                C!["btn", "input-group-btn"],
+                ev(Ev::Click, |_| Msg::AddClimber),
+                locale.t(Text::AddClimber)
+            ],
+        ],
+        p![C!["form-input-hint"], locale.t(Text::ClimbersHint)],
+    ]
+}
+
+fn view_data(data: &Data, base_url: &Url, locale: Locale) -> Node<Msg> {
+    let confirmed = data.passphrase_confirmed();
+
+    div![
+        C!["main card"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Data)]
+        ],
+        div![
+            C!["card-body", "modal-buttons"],
+            button![
+                C!["btn", "btn-primary"],
+                ev(Ev::Click, move |_| Msg::ExportData()),
+                locale.t(Text::ExportData)
+            ],
+            button![
+                C!["btn"],
+                attrs! {
+                    At::Disabled => (!confirmed).as_at_value();
+                },
+                ev(Ev::Click, move |_| Msg::ExportArchive()),
+                locale.t(Text::ExportArchive)
+            ],
+            button![
+                C!["btn"],
+                ev(Ev::Click, move |_| Msg::StartImportData()),
+                locale.t(Text::ImportData)
+            ],
+            a![
+                C!["btn"],
+                attrs! {
+                    At::Href => Urls::new(base_url).backups()
+                },
+                locale.t(Text::Backups)
+            ],
+            a![
+                C!["btn"],
+                attrs! {
+                    At::Href => Urls::new(base_url).duplicates()
+                },
+                locale.t(Text::Duplicates)
+            ],
+        ],
+        div![
+            C!["card-body"],
+            div![
+                C!["form-group"],
+                label![C!["form-label"], locale.t(Text::Passphrase)],
+                input![
+                    C!["form-input"],
+                    attrs! {
+                        At::Type => "password";
+                        At::Value => data.passphrase;
+                        At::Placeholder => locale.t(Text::PassphrasePlaceholder);
+                        At::AutoComplete => "new-password";
+                    },
+                    input_ev(Ev::Input, Msg::PassphraseChanged),
+                ],
+            ],
+            IF!(!data.passphrase.is_empty() => div![
+                C![
+                    "form-group",
+                    IF!(!confirmed && !data.passphrase_confirmation.is_empty() => "has-error")
+                ],
+                label![C!["form-label"], locale.t(Text::PassphraseConfirmation)],
+                input![
+                    C!["form-input"],
+                    attrs! {
+                        At::Type => "password";
+                        At::Value => data.passphrase_confirmation;
+                        At::AutoComplete => "new-password";
+                    },
+                    input_ev(Ev::Input, Msg::PassphraseConfirmationChanged),
+                ],
+                IF!(!confirmed => p![
+                    C!["form-input-hint"],
+                    locale.t(Text::PassphraseMismatch)
+                ]),
+            ]),
+            IF!(data.pending_import.is_some() => button![
+                C!["btn", "btn-primary"],
+                ev(Ev::Click, move |_| Msg::RetryImport),
+                locale.t(Text::DecryptAndImport)
+            ]),
+        ],
+        data.import_error.as_ref().map(|error| {
+            div![
+                C!["card-footer"],
+                div![C!["toast", "toast-error"], error.message(locale)]
             ]
+        }),
+    ]
+}
+
+fn view_day(
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+    date: NaiveDate,
+    base_url: &Url,
+) -> Node<Msg> {
+    let mut ticks: Vec<(&RouteId, &Route, &Tick)> = routes
+        .iter()
+        .flat_map(|(route_id, route)| route.ticks.iter().map(move |tick| (route_id, route, tick)))
+        .filter(|(_k, _v, tick)| settings.climbing_day(tick.timestamp) == date)
+        .collect();
+
+    ticks.sort_by_key(|(_k, _v, tick)| tick.timestamp);
+
+    let locale = settings.locale();
+
+    let sends = ticks
+        .iter()
+        .filter(|(_k, _v, tick)| matches!(tick.typ, TickType::Ascent))
+        .count();
+
+    div![
+        C!["main card"],
+        div![
+            C!["card-header"],
+            div![
+                C!["h5", "card-title"],
+                date.format(locale.t(Text::DayTitleFormat)).to_string()
+            ],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.fill(Text::DaySummary, &[&sends, &(ticks.len() - sends)])
+            ],
         ],
+        if ticks.is_empty() {
+            div![C!["card-body", "text-gray"], locale.t(Text::NoTicksOnDay)]
+        } else {
+            ul![
+                C!["route-list"],
+                ticks
+                    .into_iter()
+                    .map(|(route_id, route, tick)| {
+                        li![div![
+                            C!["view"],
+                            div![
+                                C![route.color.as_str(), "color-flag"],
+                                div![route.section.as_str()],
+                                div![route.grade.as_str()],
+                            ],
+                            label![
+                                a![
+                                    attrs! {
+                                        At::Href => Urls::new(base_url).route(route_id)
+                                    },
+                                    route.title.as_str()
+                                ],
+                                IF!(!tick.note.is_empty() => div![small![tick.note.as_str()]]),
+                            ],
+                            div![
+                                C!["stats"],
+                                div![
+                                    locale.t(tick.typ.label()),
+                                    " ",
+                                    Local
+                                        .timestamp_opt(tick.timestamp, 0)
+                                        .single()
+                                        .map(|time| time.format("%H:%M").to_string())
+                                ],
+                                div![tick.styles.join(" ")],
+                            ],
+                        ]]
+                    })
+                    .collect::<Vec<Node<Msg>>>()
+            ]
+        }
     ]
 }
 
-fn view_aggregate(routes: &IndexMap<RouteId, Route>) -> Node<Msg> {
-    let midnight = Local::now()
-        .with_hour(0)
-        .unwrap()
-        .with_minute(0)
-        .unwrap()
-        .with_second(0)
-        .unwrap()
-        .timestamp();
+fn view_aggregate(routes: &IndexMap<RouteId, Route>, settings: &Settings) -> Node<Msg> {
+    let locale = settings.locale();
+    let current_day = settings.today();
+    let current_week = settings.week_start(current_day);
 
     let mut today = 0;
+    let mut week = 0;
     let mut total = 0;
+    let mut by_style: IndexMap<&str, (i32, i32)> = IndexMap::new();
 
     for tick in routes.iter().flat_map(|route| &route.1.ticks) {
-        match tick.typ {
-            TickType::Ascent if tick.timestamp > midnight => {
-                today += 1;
-                total += 1;
-            }
-            TickType::Ascent => {
-                total += 1;
+        if let TickType::Attempt = tick.typ {
+            continue;
+        }
+
+        let day = settings.climbing_day(tick.timestamp);
+
+        total += 1;
+        if day == current_day {
+            today += 1;
+        }
+        if settings.week_start(day) == current_week {
+            week += 1;
+        }
+
+        for style in &tick.styles {
+            let counts = by_style.entry(style.as_str()).or_insert((0, 0));
+            if day == current_day {
+                counts.0 += 1;
             }
-            _ => {}
+            counts.1 += 1;
         }
     }
 
     div![
         C!["aggregate", "card"],
-        div![C!["card-header"], div![C!["h5", "card-title"], "Stats"]],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Stats)]
+        ],
         div![
             C!["card-body"],
             table![
-                tr![td!["Sends Today"], td![format!("{}", today)]],
-                tr![td!["Sends Total"], td![format!("{}", total)]]
+                tr![td![locale.t(Text::SendsToday)], td![format!("{}", today)]],
+                tr![td![locale.t(Text::SendsThisWeek)], td![format!("{}", week)]],
+                tr![td![locale.t(Text::SendsTotal)], td![format!("{}", total)]]
             ],
+            view_climber_stats(routes, settings),
+            IF!(!by_style.is_empty() => table![
+                C!["table"],
+                thead![tr![
+                    th![locale.t(Text::Style)],
+                    th![locale.t(Text::Today)],
+                    th![locale.t(Text::Total)]
+                ]],
+                tbody![settings
+                    .tick_styles
+                    .iter()
+                    .filter_map(|(key, style)| {
+                        by_style.get(key.as_str()).map(|(today, total)| {
+                            tr![
+                                td![style.label.as_str()],
+                                td![format!("{}", today)],
+                                td![format!("{}", total)]
+                            ]
+                        })
+                    })
+                    .collect::<Vec<Node<Msg>>>()]
+            ]),
         ]
     ]
 }
 
+/// Sends today, this week and in total for each climber.
+fn view_climber_stats(routes: &IndexMap<RouteId, Route>, settings: &Settings) -> Node<Msg> {
+    if settings.climbers.len() < 2 {
+        return empty![];
+    }
+
+    let locale = settings.locale();
+    let current_day = settings.today();
+    let current_week = settings.week_start(current_day);
+
+    let mut counts: HashMap<&String, (i32, i32, i32)> = HashMap::new();
+
+    for tick in routes.values().flat_map(|route| &route.ticks) {
+        if let TickType::Attempt = tick.typ {
+            continue;
+        }
+
+        let climber = match settings.tick_climber(tick) {
+            Some(climber) => climber,
+            None => continue,
+        };
+        let day = settings.climbing_day(tick.timestamp);
+        let counts = counts.entry(climber).or_default();
+
+        if day == current_day {
+            counts.0 += 1;
+        }
+        if settings.week_start(day) == current_week {
+            counts.1 += 1;
+        }
+        counts.2 += 1;
+    }
+
+    table![
+        C!["table", "climber-stats"],
+        thead![tr![
+            th![locale.t(Text::Climber)],
+            th![locale.t(Text::Today)],
+            th![locale.t(Text::ThisWeek)],
+            th![locale.t(Text::Total)]
+        ]],
+        tbody![settings
+            .climbers
+            .iter()
+            .map(|(key, climber)| {
+                let (today, week, total) = counts.get(key).copied().unwrap_or_default();
+
+                tr![
+                    td![climber.name.as_str()],
+                    td![format!("{}", today)],
+                    td![format!("{}", week)],
+                    td![format!("{}", total)]
+                ]
+            })
+            .collect::<Vec<Node<Msg>>>()]
+    ]
+}
+
 // ------ footer ------
 
-fn view_footer() -> Node<Msg> {
+fn view_footer(locale: Locale) -> Node<Msg> {
     footer![
         C!["footer", "grid-sm", "info"],
         p![
-            "created by ",
+            locale.t(Text::CreatedBy),
             a![
                 attrs! {
                     At::Href => "https://github.com/rparrett/"
                 },
                 "rob parrett"
             ],
-            " with ",
+            locale.t(Text::With),
             a![
                 attrs! {
                     At::Href => "https://github.com/seed-rs/"
@@ -775,40 +3585,23 @@ fn view_footer() -> Node<Msg> {
                 "seed-rs"
             ],
         ],
-        p![
-            a![
-                attrs! {
-                    At::Href => "#"
-                },
-                "export data",
-                ev(Ev::Click, move |_| Msg::ExportData()),
-            ],
-            "\u{00A0}\u{00B7}\u{00A0}",
-            a![
-                attrs! {
-                    At::Href => "#"
-                },
-                "import data",
-                ev(Ev::Click, move |_| Msg::StartImportData()),
-            ]
-        ]
     ]
 }
 
-fn view_pwa_ad(pwa_ad: bool) -> Node<Msg> {
+fn view_pwa_ad(pwa_ad: bool, locale: Locale) -> Node<Msg> {
     let ua = window().navigator().user_agent().unwrap();
     let iphone = ua.contains("iPhone OS");
 
     let msg_one = if iphone {
-        "Tap the here and choose \"Add to Home Screen.\""
+        locale.t(Text::PwaTapHere)
     } else {
-        "Add this app to your Home Screen."
+        locale.t(Text::PwaAddToHomeScreen)
     };
 
     let msg_two = if iphone {
-        "Your iPhone will be less likely to delete your data that way."
+        locale.t(Text::PwaIphoneReason)
     } else {
-        "It's better that way!"
+        locale.t(Text::PwaReason)
     };
 
     if pwa_ad {
@@ -834,10 +3627,55 @@ fn view_pwa_ad(pwa_ad: bool) -> Node<Msg> {
     }
 }
 
+fn view_update_prompt(update: bool, locale: Locale) -> Node<Msg> {
+    if !update {
+        return empty![];
+    }
+
+    div![
+        C!["toast", "toast-primary", "update-prompt"],
+        locale.t(Text::UpdateAvailable),
+        " ",
+        button![
+            C!["btn", "btn-sm"],
+            ev(Ev::Click, |_| Msg::ReloadForUpdate),
+            locale.t(Text::Reload)
+        ],
+    ]
+}
+
 #[wasm_bindgen]
 extern "C" {
     fn exportData(data: String);
     fn startImportData();
+    fn exportArchive(archive: &[u8], file_name: &str);
+    fn readAllPhotos() -> js_sys::Promise;
+    fn storePhoto(route_id: String, bytes: &[u8], mime: String);
+    fn startAddPhoto(route_id: String) -> js_sys::Promise;
+    fn startAddTeammates() -> js_sys::Promise;
+    fn loadTeam() -> js_sys::Promise;
+    fn saveTeam(json: String) -> js_sys::Promise;
+    fn loadPhoto(route_id: String) -> js_sys::Promise;
+    fn deletePhoto(route_id: String);
+    fn deletePhotosExcept(route_ids: js_sys::Array);
+    fn waitForUpdate(registration: &JsValue) -> js_sys::Promise;
+    fn checkStorage(ask_to_persist: bool) -> js_sys::Promise;
+}
+
+/// Converts the `[routeId, bytes, mimeType]` triples from `readAllPhotos`.
+fn archive_photos(photos: JsValue) -> Vec<ArchivePhoto> {
+    js_sys::Array::from(&photos)
+        .iter()
+        .filter_map(|photo| {
+            let photo = js_sys::Array::from(&photo);
+
+            Some(ArchivePhoto {
+                route_id: photo.get(0).as_string()?,
+                bytes: js_sys::Uint8Array::new(&photo.get(1)).to_vec(),
+                mime: photo.get(2).as_string()?,
+            })
+        })
+        .collect()
 }
 
 // ------ ------
@@ -855,8 +3693,11 @@ fn create_closures_for_js(app: &App<Msg, Model, Vec<Node<Msg>>>) -> Box<[JsValue
     let import_data = wrap_in_permanent_closure(enc!((app) move |data| {
         app.update(Msg::ImportData(data))
     }));
+    let import_archive = wrap_in_permanent_closure(enc!((app) move |bytes| {
+        app.update(Msg::ImportArchive(bytes))
+    }));
 
-    vec![import_data].into_boxed_slice()
+    vec![import_data, import_archive].into_boxed_slice()
 }
 
 fn wrap_in_permanent_closure<T>(f: impl FnMut(T) + 'static) -> JsValue
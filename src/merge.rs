@@ -0,0 +1,617 @@
+//! Merging routes that are the same climb and splitting ticks off routes that were used for
+//! two. Two routes up with the same section, color and grade are almost always one route
+//! entered twice, so those are found for merging too.
+//!
+//! Both operations record an `Undo` with the routes as they were.
+
+use chrono::{Local, TimeZone};
+use indexmap::IndexMap;
+use itertools::Itertools;
+use seed::{prelude::*, *};
+use std::mem;
+
+use crate::i18n::{Locale, Text};
+use crate::{Msg, Route, RouteId, Settings, Tick, Urls};
+
+/// The merge and split panel on a route's page.
+#[derive(Default)]
+pub struct RouteTools {
+    pub open: bool,
+    pub merge_with: Option<RouteId>,
+    /// Whether the merged route takes the other route's title, grade and so on.
+    pub details_from_other: bool,
+    /// Indices of the ticks to split off.
+    pub split_ticks: Vec<usize>,
+}
+
+/// The routes a merge or split changed, as they were before it.
+pub struct Undo {
+    text: Text,
+    count: usize,
+    /// Each route with its position in the route list.
+    routes: Vec<(usize, RouteId, Route)>,
+    created: Vec<RouteId>,
+}
+
+impl Undo {
+    fn record(
+        routes: &IndexMap<RouteId, Route>,
+        route_ids: &[RouteId],
+        text: Text,
+        count: usize,
+    ) -> Self {
+        Undo {
+            text,
+            count,
+            routes: route_ids
+                .iter()
+                .filter_map(|route_id| {
+                    routes
+                        .get_full(route_id)
+                        .map(|(index, route_id, route)| (index, *route_id, route.clone()))
+                })
+                .sorted_by_key(|(index, _route_id, _route)| *index)
+                .collect(),
+            created: vec![],
+        }
+    }
+
+    pub fn apply(self, routes: &mut IndexMap<RouteId, Route>) {
+        for route_id in &self.created {
+            routes.shift_remove(route_id);
+        }
+
+        for (index, route_id, route) in self.routes {
+            let (current, _old) = routes.insert_full(route_id, route);
+            routes.move_index(current, index.min(routes.len() - 1));
+        }
+    }
+}
+
+/// A route that's up with the given section, color and grade.
+pub fn find_duplicate(
+    routes: &IndexMap<RouteId, Route>,
+    section: &str,
+    color: &str,
+    grade: &str,
+) -> Option<RouteId> {
+    routes
+        .iter()
+        .find(|(_k, route)| {
+            !route.retired
+                && route.section == section
+                && route.color == color
+                && route.grade == grade
+        })
+        .map(|(route_id, _route)| *route_id)
+}
+
+/// Groups of routes up with the same section, color and grade. The route to keep comes first
+/// in each group: one with a photo, since photos can't be moved, then the one with the most
+/// ticks, then the oldest.
+pub fn duplicate_groups(routes: &IndexMap<RouteId, Route>) -> Vec<Vec<RouteId>> {
+    routes
+        .iter()
+        .filter(|(_k, route)| !route.retired)
+        .map(|(route_id, route)| {
+            (
+                (&route.section, &route.color, &route.grade),
+                (route_id, route),
+            )
+        })
+        .into_group_map()
+        .into_iter()
+        .filter(|(_key, group)| group.len() > 1)
+        .map(|(_key, group)| {
+            group
+                .into_iter()
+                .sorted_by_key(|(_k, route)| {
+                    (
+                        route.photo.is_none(),
+                        std::cmp::Reverse(route.ticks.len()),
+                        route.created_at,
+                    )
+                })
+                .map(|(route_id, _route)| *route_id)
+                .collect()
+        })
+        .sorted_by_key(|group: &Vec<RouteId>| routes.get_index_of(&group[0]))
+        .collect()
+}
+
+/// Merges each of `others` into `keep`, returning how to undo it.
+pub fn merge_all(
+    routes: &mut IndexMap<RouteId, Route>,
+    keep: RouteId,
+    others: &[RouteId],
+    details_from_other: bool,
+) -> Undo {
+    let route_ids: Vec<RouteId> = Some(keep)
+        .into_iter()
+        .chain(others.iter().copied())
+        .collect();
+    let undo = Undo::record(routes, &route_ids, Text::MergedRoutes, route_ids.len());
+
+    for other in others {
+        merge(routes, keep, *other, details_from_other);
+    }
+
+    undo
+}
+
+/// Moves `other`'s ticks onto `keep` and removes `other`. `keep` stays where it is in the list
+/// and keeps its photo. Its details, like the title and grade, come from `other` if
+/// `details_from_other` is set, and anything missing is filled in from the other route.
+fn merge(
+    routes: &mut IndexMap<RouteId, Route>,
+    keep: RouteId,
+    other: RouteId,
+    details_from_other: bool,
+) {
+    if keep == other || !routes.contains_key(&keep) {
+        return;
+    }
+    let mut other = match routes.shift_remove(&other) {
+        Some(other) => other,
+        None => return,
+    };
+    let route = match routes.get_mut(&keep) {
+        Some(route) => route,
+        None => return,
+    };
+
+    if details_from_other {
+        swap_details(route, &mut other);
+    }
+
+    route.ticks.extend(other.ticks);
+    route.ticks.sort_by_key(|tick| tick.timestamp);
+
+    if route.title.is_empty() {
+        route.title = other.title;
+    }
+    if route.beta.is_empty() {
+        route.beta = other.beta;
+    }
+    route.rating = route.rating.or(other.rating);
+    route.grade_opinion = route.grade_opinion.or(other.grade_opinion);
+    route.project |= other.project;
+    route.completed |= other.completed;
+    route.set_on = route.set_on.into_iter().chain(other.set_on).min();
+    route.expected_strip = route.expected_strip.or(other.expected_strip);
+    if other.created_at > 0 && (route.created_at == 0 || other.created_at < route.created_at) {
+        route.created_at = other.created_at;
+    }
+}
+
+fn swap_details(a: &mut Route, b: &mut Route) {
+    mem::swap(&mut a.title, &mut b.title);
+    mem::swap(&mut a.color, &mut b.color);
+    mem::swap(&mut a.section, &mut b.section);
+    mem::swap(&mut a.grade, &mut b.grade);
+    mem::swap(&mut a.beta, &mut b.beta);
+    mem::swap(&mut a.rating, &mut b.rating);
+    mem::swap(&mut a.grade_opinion, &mut b.grade_opinion);
+    mem::swap(&mut a.project, &mut b.project);
+    mem::swap(&mut a.set_on, &mut b.set_on);
+    mem::swap(&mut a.expected_strip, &mut b.expected_strip);
+}
+
+/// A tick and its position in its route.
+type IndexedTick = (usize, Tick);
+
+/// Moves the ticks at `tick_indices` to a new route with the same section, color, grade and
+/// dates, placed right after the original. Returns the new route's id and how to undo it, or
+/// `None` if that would move none of the ticks or all of them.
+pub fn split(
+    routes: &mut IndexMap<RouteId, Route>,
+    route_id: RouteId,
+    tick_indices: &[usize],
+) -> Option<(RouteId, Undo)> {
+    let (index, _route_id, route) = routes.get_full_mut(&route_id)?;
+
+    let moves_all = (0..route.ticks.len()).all(|i| tick_indices.contains(&i));
+    if tick_indices.is_empty() || moves_all || tick_indices.iter().any(|i| *i >= route.ticks.len())
+    {
+        return None;
+    }
+
+    let mut undo = Undo {
+        text: Text::SplitRoute,
+        count: tick_indices.len(),
+        routes: vec![(index, route_id, route.clone())],
+        created: vec![],
+    };
+
+    let (moved, kept): (Vec<IndexedTick>, Vec<IndexedTick>) = mem::take(&mut route.ticks)
+        .into_iter()
+        .enumerate()
+        .partition(|(i, _tick)| tick_indices.contains(i));
+    route.ticks = kept.into_iter().map(|(_i, tick)| tick).collect();
+
+    let new_route = Route {
+        title: String::new(),
+        completed: false,
+        color: route.color.clone(),
+        section: route.section.clone(),
+        grade: route.grade.clone(),
+        ticks: moved.into_iter().map(|(_i, tick)| tick).collect(),
+        retired: route.retired,
+        beta: String::new(),
+        rating: None,
+        grade_opinion: None,
+        project: false,
+        created_at: route.created_at,
+        set_on: route.set_on,
+        expected_strip: route.expected_strip,
+        photo: None,
+    };
+
+    let new_id = RouteId::new_v4();
+    let (new_index, _old) = routes.insert_full(new_id, new_route);
+    routes.move_index(new_index, index + 1);
+    undo.created.push(new_id);
+
+    Some((new_id, undo))
+}
+
+pub fn view_duplicates(
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+    base_url: &Url,
+) -> Node<Msg> {
+    let locale = settings.locale();
+    let groups = duplicate_groups(routes);
+
+    div![
+        C!["main card", "duplicates"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Duplicates)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.t(Text::DuplicatesCaption)
+            ],
+        ],
+        if groups.is_empty() {
+            div![C!["card-body", "text-gray"], locale.t(Text::NoDuplicates)]
+        } else {
+            div![groups
+                .into_iter()
+                .map(|group| {
+                    let first = &routes[&group[0]];
+
+                    ul![
+                        C!["route-list"],
+                        li![div![
+                            C!["view"],
+                            div![
+                                C![first.color.as_str(), "color-flag"],
+                                div![first.section.as_str()],
+                                div![first.grade.as_str()],
+                            ],
+                            label![group
+                                .iter()
+                                .map(|route_id| {
+                                    let route = &routes[route_id];
+
+                                    div![
+                                        a![
+                                            attrs! {
+                                                At::Href => Urls::new(base_url).route(route_id)
+                                            },
+                                            route.name()
+                                        ],
+                                        small![
+                                            C!["text-gray"],
+                                            " ",
+                                            locale.fill(Text::RouteTicks, &[&route.ticks.len()]),
+                                            route
+                                                .set_timestamp()
+                                                .and_then(|set| Local
+                                                    .timestamp_opt(set, 0)
+                                                    .single())
+                                                .map(|set| format!(", {}", set.format("%Y-%m-%d")))
+                                                .unwrap_or_default(),
+                                        ],
+                                    ]
+                                })
+                                .collect::<Vec<Node<Msg>>>()],
+                            button![
+                                C!["tick-button btn btn-sm btn-primary"],
+                                ev(Ev::Click, move |_| Msg::MergeDuplicates(group)),
+                                locale.t(Text::Merge)
+                            ],
+                        ]]
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>()]
+        },
+    ]
+}
+
+/// The merge and split panel on a route's page.
+pub fn view_route_tools(
+    route_id: RouteId,
+    route: &Route,
+    routes: &IndexMap<RouteId, Route>,
+    tools: &RouteTools,
+    locale: Locale,
+) -> Node<Msg> {
+    let others = routes
+        .iter()
+        .filter(|(k, v)| **k != route_id && !v.retired)
+        .sorted_by(|(_a, a), (_b, b)| {
+            (&a.section, &a.grade, &a.color).cmp(&(&b.section, &b.grade, &b.color))
+        });
+
+    let merge_with = tools.merge_with;
+    let details_from_other = tools.details_from_other;
+    let split_ticks = tools.split_ticks.clone();
+
+    div![
+        C!["route-tools"],
+        div![
+            C!["form-group"],
+            label![C!["form-label"], locale.t(Text::MergeWith)],
+            select![
+                C!["form-select"],
+                option![
+                    attrs! {
+                        At::Value => "";
+                        At::Selected => merge_with.is_none().as_at_value();
+                    },
+                    locale.t(Text::ChooseRoute)
+                ],
+                others
+                    .map(|(other_id, other)| option![
+                        attrs! {
+                            At::Value => other_id.to_string();
+                            At::Selected => (merge_with == Some(*other_id)).as_at_value();
+                        },
+                        format!(
+                            "{} \u{b7} {} \u{b7} {} \u{b7} {}",
+                            other.section,
+                            other.color,
+                            other.grade,
+                            other.name()
+                        )
+                    ])
+                    .collect::<Vec<Node<Msg>>>(),
+                input_ev(Ev::Change, |value| Msg::ChooseMergeRoute(
+                    RouteId::parse_str(&value).ok()
+                )),
+            ],
+            [
+                (false, Text::KeepTheseDetails),
+                (true, Text::UseOtherDetails)
+            ]
+            .iter()
+            .map(|(from_other, text)| {
+                let from_other = *from_other;
+
+                label![
+                    C!["form-radio"],
+                    input![
+                        attrs! {
+                            At::Type => "radio";
+                            At::Checked => (details_from_other == from_other).as_at_value();
+                        },
+                        ev(Ev::Change, move |_| Msg::ChooseMergeDetails(from_other)),
+                    ],
+                    i![C!["form-icon"]],
+                    locale.t(*text)
+                ]
+            })
+            .collect::<Vec<Node<Msg>>>(),
+            button![
+                C!["btn", "btn-primary"],
+                attrs! {
+                    At::Disabled => merge_with.is_none().as_at_value();
+                },
+                ev(Ev::Click, move |_| Msg::MergeRoute(route_id)),
+                locale.t(Text::Merge)
+            ],
+        ],
+        IF!(route.ticks.len() > 1 => div![
+            C!["form-group"],
+            label![C!["form-label"], locale.t(Text::SplitCaption)],
+            route
+                .ticks
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(index, tick)| label![
+                    C!["form-checkbox"],
+                    input![
+                        attrs! {
+                            At::Type => "checkbox";
+                            At::Checked => split_ticks.contains(&index).as_at_value();
+                        },
+                        ev(Ev::Change, move |_| Msg::ToggleSplitTick(index)),
+                    ],
+                    i![C!["form-icon"]],
+                    format!(
+                        "{} {}",
+                        Local
+                            .timestamp_opt(tick.timestamp, 0)
+                            .single()
+                            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_default(),
+                        locale.t(tick.typ.label())
+                    )
+                ])
+                .collect::<Vec<Node<Msg>>>(),
+            button![
+                C!["btn"],
+                attrs! {
+                    At::Disabled => (split_ticks.is_empty() || split_ticks.len() == route.ticks.len())
+                        .as_at_value();
+                },
+                ev(Ev::Click, move |_| Msg::SplitRoute(route_id)),
+                locale.fill(Text::SplitOff, &[&split_ticks.len()])
+            ],
+        ]),
+    ]
+}
+
+/// The last merge or split, with a button to undo it.
+pub fn view_undo(undo: Option<&Undo>, locale: Locale) -> Node<Msg> {
+    let undo = match undo {
+        Some(undo) => undo,
+        None => return empty![],
+    };
+
+    div![
+        C!["toast", "undo-toast"],
+        button![
+            C!["btn", "btn-clear", "float-right"],
+            ev(Ev::Click, |_| Msg::DismissUndo)
+        ],
+        locale.fill(undo.text, &[&undo.count]),
+        " ",
+        button![
+            C!["btn", "btn-sm"],
+            ev(Ev::Click, |_| Msg::Undo),
+            locale.t(Text::Undo)
+        ],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TickType;
+
+    fn tick(timestamp: i64) -> Tick {
+        Tick {
+            typ: TickType::Attempt,
+            timestamp,
+            styles: vec![],
+            note: String::new(),
+            climber: None,
+        }
+    }
+
+    fn route(title: &str, timestamps: &[i64]) -> Route {
+        Route {
+            title: title.to_string(),
+            completed: false,
+            color: "red".to_string(),
+            section: "AB1".to_string(),
+            grade: "5".to_string(),
+            ticks: timestamps
+                .iter()
+                .map(|timestamp| tick(*timestamp))
+                .collect(),
+            retired: false,
+            beta: String::new(),
+            rating: None,
+            grade_opinion: None,
+            project: false,
+            created_at: 0,
+            set_on: None,
+            expected_strip: None,
+            photo: None,
+        }
+    }
+
+    fn routes(list: Vec<Route>) -> (IndexMap<RouteId, Route>, Vec<RouteId>) {
+        let routes: IndexMap<RouteId, Route> = list
+            .into_iter()
+            .map(|route| (RouteId::new_v4(), route))
+            .collect();
+        let ids = routes.keys().copied().collect();
+
+        (routes, ids)
+    }
+
+    fn titles(routes: &IndexMap<RouteId, Route>) -> Vec<&str> {
+        routes.values().map(|route| route.title.as_str()).collect()
+    }
+
+    fn timestamps(route: &Route) -> Vec<i64> {
+        route.ticks.iter().map(|tick| tick.timestamp).collect()
+    }
+
+    #[test]
+    fn merge_all_moves_ticks_and_removes_the_others() {
+        let (mut routes, ids) = routes(vec![
+            route("a", &[3]),
+            route("", &[1, 5]),
+            route("c", &[2]),
+            route("d", &[4]),
+        ]);
+
+        merge_all(&mut routes, ids[1], &[ids[0], ids[3]], false);
+
+        assert_eq!(titles(&routes), vec!["a", "c"]);
+        assert_eq!(timestamps(&routes[&ids[1]]), vec![1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn merge_takes_details_from_the_other_route() {
+        let mut other = route("other", &[2]);
+        other.grade = "6".to_string();
+        let (mut routes, ids) = routes(vec![route("keep", &[1]), other]);
+
+        merge_all(&mut routes, ids[0], &[ids[1]], true);
+
+        let merged = &routes[&ids[0]];
+        assert_eq!(merged.title, "other");
+        assert_eq!(merged.grade, "6");
+        assert_eq!(timestamps(merged), vec![1, 2]);
+    }
+
+    #[test]
+    fn undoing_a_merge_puts_the_routes_back_in_place() {
+        let (mut routes, ids) = routes(vec![
+            route("a", &[1]),
+            route("b", &[2]),
+            route("c", &[3]),
+            route("d", &[4]),
+        ]);
+
+        let undo = merge_all(&mut routes, ids[2], &[ids[0], ids[3]], true);
+        undo.apply(&mut routes);
+
+        assert_eq!(routes.keys().copied().collect::<Vec<_>>(), ids);
+        assert_eq!(titles(&routes), vec!["a", "b", "c", "d"]);
+        assert_eq!(timestamps(&routes[&ids[2]]), vec![3]);
+    }
+
+    #[test]
+    fn split_moves_ticks_to_a_new_route_after_the_original() {
+        let (mut routes, ids) = routes(vec![route("a", &[1, 2, 3]), route("b", &[4])]);
+
+        let (new_id, _undo) = split(&mut routes, ids[0], &[0, 2]).unwrap();
+
+        assert_eq!(routes.get_index_of(&new_id), Some(1));
+        assert_eq!(timestamps(&routes[&ids[0]]), vec![2]);
+        assert_eq!(timestamps(&routes[&new_id]), vec![1, 3]);
+        assert_eq!(routes[&new_id].section, "AB1");
+    }
+
+    #[test]
+    fn undoing_a_split_removes_the_new_route() {
+        let (mut routes, ids) = routes(vec![route("a", &[1, 2, 3]), route("b", &[4])]);
+
+        let (_new_id, undo) = split(&mut routes, ids[0], &[1]).unwrap();
+        undo.apply(&mut routes);
+
+        assert_eq!(routes.keys().copied().collect::<Vec<_>>(), ids);
+        assert_eq!(timestamps(&routes[&ids[0]]), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn split_needs_some_ticks_to_stay() {
+        let (mut routes, ids) = routes(vec![route("a", &[1, 2])]);
+
+        assert!(split(&mut routes, ids[0], &[]).is_none());
+        assert!(split(&mut routes, ids[0], &[0, 1]).is_none());
+        assert!(split(&mut routes, ids[0], &[1, 0, 1]).is_none());
+        assert!(split(&mut routes, ids[0], &[2]).is_none());
+        assert_eq!(routes.len(), 1);
+        assert_eq!(timestamps(&routes[&ids[0]]), vec![1, 2]);
+    }
+}
@@ -0,0 +1,74 @@
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+use crate::{Msg, RouteId};
+
+/// A route's photo. The image itself lives in IndexedDB, keyed by route id, so only the
+/// annotations are kept in LocalStorage.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct Photo {
+    #[serde(default)]
+    pub holds: Vec<HoldCircle>,
+}
+
+/// A circled hold. Coordinates are fractions of the photo's width and height so they survive
+/// the photo being shown at any size.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct HoldCircle {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// The photo with its hold circles drawn over it. Tapping the photo circles a hold and
+/// tapping a circle removes it.
+pub fn view_photo(route_id: RouteId, photo: &Photo, url: Option<&String>) -> Node<Msg> {
+    let url = match url {
+        Some(url) => url,
+        None => return div![C!["route-photo", "loading"]],
+    };
+
+    div![
+        C!["route-photo"],
+        img![attrs! {
+            At::Src => url;
+        }],
+        svg![
+            mouse_ev(Ev::Click, move |event| {
+                let target = event
+                    .current_target()?
+                    .dyn_into::<web_sys::Element>()
+                    .ok()?;
+                let width = f64::from(target.client_width());
+                let height = f64::from(target.client_height());
+
+                if width > 0.0 && height > 0.0 {
+                    Some(Msg::AddHoldCircle(
+                        route_id,
+                        f64::from(event.offset_x()) / width,
+                        f64::from(event.offset_y()) / height,
+                    ))
+                } else {
+                    None
+                }
+            }),
+            photo
+                .holds
+                .iter()
+                .enumerate()
+                .map(|(index, hold)| {
+                    circle![
+                        attrs! {
+                            At::Cx => format!("{:.2}%", hold.x * 100.0);
+                            At::Cy => format!("{:.2}%", hold.y * 100.0);
+                            At::R => "4%";
+                        },
+                        ev(Ev::Click, move |event| {
+                            event.stop_propagation();
+                            Msg::RemoveHoldCircle(route_id, index)
+                        }),
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>()
+        ],
+    ]
+}
@@ -0,0 +1,216 @@
+//! Quick log: tick a route by its section, color and grade instead of finding it in the list.
+//! A tick goes on the route up with those attributes, or on a new one if there isn't any.
+//! The choices stay put between ticks so repeated attempts are one tap each.
+
+use enclose::enc;
+use indexmap::IndexMap;
+use itertools::Itertools;
+use seed::{prelude::*, *};
+
+use crate::gym_map;
+use crate::i18n::Text;
+use crate::{Msg, Route, RouteId, Settings, TickType};
+
+#[derive(Default)]
+pub struct QuickLog {
+    pub section: Option<String>,
+    pub color: Option<String>,
+    pub grade: Option<String>,
+    /// Picked when more than one route matches.
+    pub route: Option<RouteId>,
+    pub last: Option<(RouteId, TickType)>,
+}
+
+impl QuickLog {
+    /// Routes up with the chosen section, color and grade, most recently ticked first.
+    pub fn matching_routes(&self, routes: &IndexMap<RouteId, Route>) -> Vec<RouteId> {
+        let (section, color, grade) = match (&self.section, &self.color, &self.grade) {
+            (Some(section), Some(color), Some(grade)) => (section, color, grade),
+            _ => return vec![],
+        };
+
+        routes
+            .iter()
+            .filter(|(_k, route)| {
+                !route.retired
+                    && route.section == *section
+                    && route.color == *color
+                    && route.grade == *grade
+            })
+            .sorted_by_key(|(_k, route)| {
+                std::cmp::Reverse(
+                    route
+                        .ticks
+                        .last()
+                        .map_or(route.created_at, |tick| tick.timestamp),
+                )
+            })
+            .map(|(route_id, _route)| *route_id)
+            .collect()
+    }
+
+    /// The route the next tick goes on, or `None` when a new route is needed.
+    pub fn target(&self, routes: &IndexMap<RouteId, Route>) -> Option<RouteId> {
+        let matching = self.matching_routes(routes);
+
+        self.route
+            .filter(|route_id| matching.contains(route_id))
+            .or_else(|| matching.first().copied())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.section.is_some() && self.color.is_some() && self.grade.is_some()
+    }
+}
+
+pub fn view_quick_log(
+    quick_log: &QuickLog,
+    routes: &IndexMap<RouteId, Route>,
+    settings: &Settings,
+) -> Node<Msg> {
+    let locale = settings.locale();
+    let chosen = |choice: &Option<String>, key: &str| choice.as_deref() == Some(key);
+
+    let matching = quick_log.matching_routes(routes);
+    let target = quick_log.target(routes);
+
+    let status = if !quick_log.is_complete() {
+        locale.t(Text::QuickLogPrompt).to_string()
+    } else {
+        match target.and_then(|route_id| routes.get(&route_id)) {
+            Some(route) => locale.fill(Text::QuickLogOn, &[&route.name()]),
+            None => locale.t(Text::QuickLogNewRoute).to_string(),
+        }
+    };
+
+    let last = quick_log.last.as_ref().and_then(|(route_id, typ)| {
+        let route = routes.get(route_id)?;
+        let today = settings.today();
+        let count = route
+            .ticks
+            .iter()
+            .filter(|tick| settings.climbing_day(tick.timestamp) == today)
+            .count();
+
+        Some(locale.fill(
+            Text::QuickLogged,
+            &[&locale.t(typ.label()), &route.name(), &count],
+        ))
+    });
+
+    div![
+        C!["main card", "quick-log"],
+        div![
+            C!["card-body"],
+            IF!(gym_map::has_map(&settings.sections) => gym_map::view_section_picker(
+                &settings.sections,
+                quick_log.section.as_deref().unwrap_or_default(),
+                Msg::QuickChooseSection,
+            )),
+            div![
+                C!["section-chooser"],
+                settings
+                    .sections
+                    .iter()
+                    .filter(|(_k, v)| v.polygon.is_empty())
+                    .group_by(|(_k, v)| v.group.to_owned())
+                    .into_iter()
+                    .map(|(_key, group)| {
+                        div![
+                            C!["section-chooser-row"],
+                            group
+                                .map(|(key, _section)| div![
+                                    C![
+                                        "section-chooser-item",
+                                        IF!(chosen(&quick_log.section, key) => "active"),
+                                    ],
+                                    ev(
+                                        Ev::Click,
+                                        enc!((key) move |_| Msg::QuickChooseSection(key))
+                                    ),
+                                    key
+                                ])
+                                .collect::<Vec<Node<Msg>>>()
+                        ]
+                    })
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            div![
+                C!["color-chooser"],
+                settings
+                    .colors
+                    .keys()
+                    .map(|key| div![
+                        C![key.as_str(), IF!(chosen(&quick_log.color, key) => "active")],
+                        ev(Ev::Click, enc!((key) move |_| Msg::QuickChooseColor(key)))
+                    ])
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            div![
+                C!["section-chooser"],
+                settings
+                    .grades
+                    .iter()
+                    .group_by(|(_k, v)| v.group.to_owned())
+                    .into_iter()
+                    .map(|(_key, group)| {
+                        div![
+                            C!["section-chooser-row"],
+                            group
+                                .map(|(key, _grade)| div![
+                                    C![
+                                        key.as_str(),
+                                        "section-chooser-item",
+                                        IF!(chosen(&quick_log.grade, key) => "active"),
+                                    ],
+                                    ev(Ev::Click, enc!((key) move |_| Msg::QuickChooseGrade(key))),
+                                    key
+                                ])
+                                .collect::<Vec<Node<Msg>>>()
+                        ]
+                    })
+                    .collect::<Vec<Node<Msg>>>()
+            ],
+            IF!(matching.len() > 1 => div![
+                C!["quick-log-routes"],
+                matching
+                    .iter()
+                    .filter_map(|route_id| {
+                        let route_id = *route_id;
+                        let route = routes.get(&route_id)?;
+
+                        Some(span![
+                            C!["chip", IF!(target == Some(route_id) => "active")],
+                            ev(Ev::Click, move |_| Msg::QuickChooseRoute(route_id)),
+                            route.name()
+                        ])
+                    })
+                    .collect::<Vec<Node<Msg>>>()
+            ]),
+        ],
+        div![
+            C!["card-footer"],
+            p![C!["quick-log-status"], status],
+            last.map(|last| p![C!["quick-log-status", "text-gray"], last]),
+            div![
+                C!["quick-log-buttons"],
+                button![
+                    C!["btn", "btn-primary", "btn-lg"],
+                    attrs! {
+                        At::Disabled => (!quick_log.is_complete()).as_at_value();
+                    },
+                    ev(Ev::Click, |_| Msg::QuickTick(TickType::Ascent)),
+                    locale.t(Text::Send)
+                ],
+                button![
+                    C!["btn", "btn-lg"],
+                    attrs! {
+                        At::Disabled => (!quick_log.is_complete()).as_at_value();
+                    },
+                    ev(Ev::Click, |_| Msg::QuickTick(TickType::Attempt)),
+                    locale.t(Text::Attempt)
+                ],
+            ],
+        ],
+    ]
+}
@@ -6,26 +6,35 @@ pub struct Section {
     pub group: String,
     pub label: String,
     pub sort: i32,
+    /// Outline of the wall on the gym map, as drawn in the settings. Empty if the section isn't
+    /// on the map.
+    #[serde(default)]
+    pub polygon: Vec<(i32, i32)>,
 }
 
 impl Section {
     pub fn defaults() -> IndexMap<String, Section> {
         return indexmap! {
-            "AB1".into() => Section { group: "A".into(), label: "AB1".into(), sort: 1 },
-            "AB2".into() => Section { group: "A".into(), label: "MAP".into(), sort: 2 },
-            "AB3".into() => Section { group: "A".into(), label: "AB3".into(), sort: 3 },
-            "AB4".into() => Section { group: "A".into(), label: "AB4".into(), sort: 4 },
-            "AB5".into() => Section { group: "A".into(), label: "AB5".into(), sort: 5 },
-            "AB6".into() => Section { group: "A".into(), label: "AB6".into(), sort: 6 },
-            "AB7".into() => Section { group: "A".into(), label: "AB7".into(), sort: 7 },
-            "AB8".into() => Section { group: "A".into(), label: "AB8".into(), sort: 8 },
-            "SLB".into() => Section { group: "B".into(), label: "SLB".into(), sort: 9 },
-            "LWV".into() => Section { group: "B".into(), label: "LWV".into(), sort: 10 },
-            "CAN".into() => Section { group: "B".into(), label: "CAN".into(), sort: 11 },
-            "RWV".into() => Section { group: "B".into(), label: "RWV".into(), sort: 12 },
-            "ROF".into() => Section { group: "B".into(), label: "ROF".into(), sort: 13 },
-            "GLB".into() => Section { group: "B".into(), label: "GLB".into(), sort: 14 },
-            "VRT".into() => Section { group: "B".into(), label: "VRT".into(), sort: 15 }
+            "AB1".into() => Section { group: "A".into(), label: "AB1".into(), sort: 1, polygon: Vec::new() },
+            "AB2".into() => Section { group: "A".into(), label: "MAP".into(), sort: 2, polygon: Vec::new() },
+            "AB3".into() => Section { group: "A".into(), label: "AB3".into(), sort: 3, polygon: Vec::new() },
+            "AB4".into() => Section { group: "A".into(), label: "AB4".into(), sort: 4, polygon: Vec::new() },
+            "AB5".into() => Section { group: "A".into(), label: "AB5".into(), sort: 5, polygon: Vec::new() },
+            "AB6".into() => Section { group: "A".into(), label: "AB6".into(), sort: 6, polygon: Vec::new() },
+            "AB7".into() => Section { group: "A".into(), label: "AB7".into(), sort: 7, polygon: Vec::new() },
+            "AB8".into() => Section { group: "A".into(), label: "AB8".into(), sort: 8, polygon: Vec::new() },
+            "SLB".into() => Section { group: "B".into(), label: "SLB".into(), sort: 9, polygon: Vec::new() },
+            "LWV".into() => Section { group: "B".into(), label: "LWV".into(), sort: 10, polygon: Vec::new() },
+            "CAN".into() => Section { group: "B".into(), label: "CAN".into(), sort: 11, polygon: Vec::new() },
+            "RWV".into() => Section { group: "B".into(), label: "RWV".into(), sort: 12, polygon: Vec::new() },
+            "ROF".into() => Section { group: "B".into(), label: "ROF".into(), sort: 13, polygon: Vec::new() },
+            "GLB".into() => Section { group: "B".into(), label: "GLB".into(), sort: 14, polygon: Vec::new() },
+            "VRT".into() => Section { group: "B".into(), label: "VRT".into(), sort: 15, polygon: Vec::new() }
         };
     }
+
+    /// Whether the section has an outline the user drew, enough to show it on the gym map.
+    pub fn on_map(&self) -> bool {
+        self.polygon.len() >= 3
+    }
 }
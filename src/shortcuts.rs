@@ -0,0 +1,152 @@
+//! Keyboard shortcuts for logging from a laptop. Keys are read from window keydown events
+//! and ignored while typing into a field, apart from Escape.
+
+use seed::{prelude::*, *};
+
+use crate::i18n::{Locale, Text};
+use crate::Msg;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Shortcut {
+    NewRoute,
+    Search,
+    Next,
+    Previous,
+    Send,
+    Attempt,
+    Edit,
+    Close,
+    Help,
+}
+
+impl Shortcut {
+    pub const ALL: [Shortcut; 9] = [
+        Shortcut::NewRoute,
+        Shortcut::Search,
+        Shortcut::Next,
+        Shortcut::Previous,
+        Shortcut::Send,
+        Shortcut::Attempt,
+        Shortcut::Edit,
+        Shortcut::Close,
+        Shortcut::Help,
+    ];
+
+    pub fn from_event(event: &web_sys::KeyboardEvent) -> Option<Self> {
+        if event.ctrl_key() || event.meta_key() || event.alt_key() {
+            return None;
+        }
+
+        let key = event.key();
+
+        if key == "Escape" {
+            return Some(Shortcut::Close);
+        }
+        if is_typing(event) {
+            return None;
+        }
+
+        match key.as_str() {
+            "n" => Some(Shortcut::NewRoute),
+            "/" => Some(Shortcut::Search),
+            "j" | "ArrowDown" => Some(Shortcut::Next),
+            "k" | "ArrowUp" => Some(Shortcut::Previous),
+            "s" => Some(Shortcut::Send),
+            "a" => Some(Shortcut::Attempt),
+            "e" => Some(Shortcut::Edit),
+            "?" => Some(Shortcut::Help),
+            _ => None,
+        }
+    }
+
+    fn keys(&self) -> &'static [&'static str] {
+        match self {
+            Shortcut::NewRoute => &["n"],
+            Shortcut::Search => &["/"],
+            Shortcut::Next => &["j", "\u{2193}"],
+            Shortcut::Previous => &["k", "\u{2191}"],
+            Shortcut::Send => &["s"],
+            Shortcut::Attempt => &["a"],
+            Shortcut::Edit => &["e"],
+            Shortcut::Close => &["Esc"],
+            Shortcut::Help => &["?"],
+        }
+    }
+
+    fn label(&self) -> Text {
+        match self {
+            Shortcut::NewRoute => Text::ShortcutNewRoute,
+            Shortcut::Search => Text::ShortcutSearch,
+            Shortcut::Next => Text::ShortcutNext,
+            Shortcut::Previous => Text::ShortcutPrevious,
+            Shortcut::Send => Text::ShortcutSend,
+            Shortcut::Attempt => Text::ShortcutAttempt,
+            Shortcut::Edit => Text::ShortcutEdit,
+            Shortcut::Close => Text::ShortcutClose,
+            Shortcut::Help => Text::ShortcutHelp,
+        }
+    }
+}
+
+/// Whether the key is going into a text field rather than to the app.
+fn is_typing(event: &web_sys::KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+        .is_some_and(|element| {
+            element.is_content_editable()
+                || matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        })
+}
+
+/// Moves keyboard focus to an element once it has been rendered.
+pub fn focus(selector: &str) {
+    if let Some(element) = document()
+        .query_selector(selector)
+        .ok()
+        .flatten()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        // Focusing scrolls the element into view if needed.
+        let _ = element.focus();
+    }
+}
+
+/// The `?` overlay listing every shortcut.
+pub fn view_help(open: bool, locale: Locale) -> Node<Msg> {
+    div![
+        C!["modal", "modal-sm", IF!(open => "active")],
+        a![
+            C!["modal-overlay"],
+            ev(Ev::Click, |_| Msg::ToggleShortcutHelp)
+        ],
+        div![
+            C!["modal-container"],
+            div![
+                C!["modal-header"],
+                button![
+                    C!["btn", "btn-clear", "float-right"],
+                    ev(Ev::Click, |_| Msg::ToggleShortcutHelp)
+                ],
+                div![C!["modal-title", "h5"], locale.t(Text::KeyboardShortcuts)],
+            ],
+            div![
+                C!["modal-body"],
+                table![
+                    C!["table", "shortcut-table"],
+                    tbody![Shortcut::ALL
+                        .iter()
+                        .map(|shortcut| tr![
+                            td![shortcut
+                                .keys()
+                                .iter()
+                                .map(|key| kbd![key])
+                                .collect::<Vec<Node<Msg>>>()],
+                            td![locale.t(shortcut.label())],
+                        ])
+                        .collect::<Vec<Node<Msg>>>()]
+                ]
+            ],
+        ]
+    ]
+}
@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Text;
+use crate::{Route, RouteId, Settings, TickType};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum SortOrder {
+    /// Section, then color, then grade, the way routes appear on the wall.
+    #[default]
+    Gym,
+    Grade,
+    LastActivity,
+    Attempts,
+    DateSet,
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 5] = [
+        SortOrder::Gym,
+        SortOrder::Grade,
+        SortOrder::LastActivity,
+        SortOrder::Attempts,
+        SortOrder::DateSet,
+    ];
+
+    pub fn label(self) -> Text {
+        match self {
+            SortOrder::Gym => Text::SortGym,
+            SortOrder::Grade => Text::SortGrade,
+            SortOrder::LastActivity => Text::SortActivity,
+            SortOrder::Attempts => Text::SortAttempts,
+            SortOrder::DateSet => Text::SortDateSet,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum Grouping {
+    #[default]
+    Section,
+    Grade,
+    Color,
+    Flat,
+}
+
+impl Grouping {
+    pub const ALL: [Grouping; 4] = [
+        Grouping::Section,
+        Grouping::Grade,
+        Grouping::Color,
+        Grouping::Flat,
+    ];
+
+    pub fn label(self) -> Text {
+        match self {
+            Grouping::Section => Text::GroupSection,
+            Grouping::Grade => Text::GroupGrade,
+            Grouping::Color => Text::GroupColor,
+            Grouping::Flat => Text::GroupNone,
+        }
+    }
+
+    /// The key routes are grouped by. Routes with equal keys end up in the same card.
+    pub fn key(self, route: &Route) -> Option<&str> {
+        match self {
+            Grouping::Section => Some(&route.section),
+            Grouping::Grade => Some(&route.grade),
+            Grouping::Color => Some(&route.color),
+            Grouping::Flat => None,
+        }
+    }
+
+    fn compare(self, settings: &Settings, a: &Route, b: &Route) -> Ordering {
+        match self {
+            Grouping::Section => section_sort(settings, a).cmp(&section_sort(settings, b)),
+            Grouping::Grade => grade_sort(settings, a).cmp(&grade_sort(settings, b)),
+            Grouping::Color => color_sort(settings, a).cmp(&color_sort(settings, b)),
+            Grouping::Flat => Ordering::Equal,
+        }
+    }
+}
+
+/// Returns the ids of `routes` ordered into groups and then by `order` within each group.
+pub fn sorted_route_ids<'a>(
+    routes: impl Iterator<Item = (&'a RouteId, &'a Route)>,
+    settings: &Settings,
+    order: SortOrder,
+    grouping: Grouping,
+) -> Vec<RouteId> {
+    let mut routes: Vec<(&RouteId, &Route)> = routes.collect();
+
+    routes.sort_by(|(_ak, av), (_bk, bv)| {
+        grouping
+            .compare(settings, av, bv)
+            .then_with(|| compare(order, settings, av, bv))
+    });
+
+    routes.into_iter().map(|(k, _v)| *k).collect()
+}
+
+fn compare(order: SortOrder, settings: &Settings, a: &Route, b: &Route) -> Ordering {
+    let gym = || {
+        section_sort(settings, a)
+            .cmp(&section_sort(settings, b))
+            .then(color_sort(settings, a).cmp(&color_sort(settings, b)))
+            .then(grade_sort(settings, a).cmp(&grade_sort(settings, b)))
+            .then(a.title.cmp(&b.title))
+    };
+
+    match order {
+        SortOrder::Gym => gym(),
+        SortOrder::Grade => grade_sort(settings, a)
+            .cmp(&grade_sort(settings, b))
+            .then_with(gym),
+        SortOrder::LastActivity => last_activity(b).cmp(&last_activity(a)).then_with(gym),
+        SortOrder::Attempts => attempts(b).cmp(&attempts(a)).then_with(gym),
+        SortOrder::DateSet => date_set(b).cmp(&date_set(a)).then_with(gym),
+    }
+}
+
+fn section_sort(settings: &Settings, route: &Route) -> i32 {
+    settings.sections.get(&route.section).map_or(0, |s| s.sort)
+}
+
+fn color_sort(settings: &Settings, route: &Route) -> i32 {
+    settings.colors.get(&route.color).map_or(0, |s| s.sort)
+}
+
+fn grade_sort(settings: &Settings, route: &Route) -> i32 {
+    settings.grades.get(&route.grade).map_or(0, |s| s.sort)
+}
+
+fn last_activity(route: &Route) -> i64 {
+    route
+        .ticks
+        .iter()
+        .map(|tick| tick.timestamp)
+        .max()
+        .unwrap_or(0)
+}
+
+fn attempts(route: &Route) -> usize {
+    route
+        .ticks
+        .iter()
+        .filter(|tick| matches!(tick.typ, TickType::Attempt))
+        .count()
+}
+
+fn date_set(route: &Route) -> i64 {
+    route.set_timestamp().unwrap_or(0)
+}
@@ -0,0 +1,628 @@
+//! Team leaderboards and challenges. Teammates share their exports, which are kept on this
+//! device in IndexedDB, never mixed into your data, and compared with yours.
+//!
+//! Every climber in a dataset is a separate entry, so a shared phone counts for everyone on
+//! it. Grades and sections come from each dataset's own settings.
+
+use chrono::{Datelike, TimeZone, Utc};
+use itertools::Itertools;
+use seed::{prelude::*, *};
+use serde::{Deserialize, Serialize};
+
+use crate::archive::{self, ArchiveError};
+use crate::encryption;
+use crate::i18n::{Locale, Text};
+use crate::util;
+use crate::{Msg, PersistedData, Route, Settings, Tick, TickType};
+
+#[derive(Serialize, Deserialize)]
+pub struct Teammate {
+    pub name: String,
+    pub imported_at: i64,
+    pub data: PersistedData,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum Challenge {
+    /// Send every route up in a section.
+    SectionSweep(String),
+    /// Send a number of routes this month.
+    MonthlySends(u32),
+    /// Send a number of routes of a grade.
+    GradeSends(String, u32),
+}
+
+impl Challenge {
+    fn label(&self, locale: Locale) -> String {
+        match self {
+            Challenge::SectionSweep(section) => {
+                locale.fill(Text::ChallengeSectionSweep, &[section])
+            }
+            Challenge::MonthlySends(count) => locale.fill(Text::ChallengeMonthlySends, &[count]),
+            Challenge::GradeSends(grade, count) => {
+                locale.fill(Text::ChallengeGradeSends, &[count, grade])
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChallengeKind {
+    SectionSweep,
+    MonthlySends,
+    GradeSends,
+}
+
+impl ChallengeKind {
+    const ALL: [ChallengeKind; 3] = [
+        ChallengeKind::SectionSweep,
+        ChallengeKind::MonthlySends,
+        ChallengeKind::GradeSends,
+    ];
+
+    fn label(&self) -> Text {
+        match self {
+            ChallengeKind::SectionSweep => Text::ChallengeKindSection,
+            ChallengeKind::MonthlySends => Text::ChallengeKindMonthly,
+            ChallengeKind::GradeSends => Text::ChallengeKindGrade,
+        }
+    }
+}
+
+/// The new challenge form.
+pub struct ChallengeDraft {
+    pub kind: ChallengeKind,
+    pub section: String,
+    pub grade: String,
+    pub count: String,
+}
+
+impl Default for ChallengeDraft {
+    fn default() -> Self {
+        ChallengeDraft {
+            kind: ChallengeKind::SectionSweep,
+            section: String::new(),
+            grade: String::new(),
+            count: "10".to_string(),
+        }
+    }
+}
+
+impl ChallengeDraft {
+    pub fn to_challenge(&self, settings: &Settings) -> Option<Challenge> {
+        // Each kind only needs its own fields, so a gym without grades can still have
+        // monthly challenges.
+        let count = || self.count.trim().parse().ok().filter(|count| *count > 0);
+        let section = || {
+            Some(&self.section)
+                .filter(|section| !section.is_empty())
+                .or_else(|| settings.sections.keys().next())
+        };
+        let grade = || {
+            Some(&self.grade)
+                .filter(|grade| !grade.is_empty())
+                .or_else(|| settings.grades.keys().next())
+        };
+
+        match self.kind {
+            ChallengeKind::SectionSweep => {
+                section().map(|section| Challenge::SectionSweep(section.clone()))
+            }
+            ChallengeKind::MonthlySends => count().map(Challenge::MonthlySends),
+            ChallengeKind::GradeSends => grade()
+                .zip(count())
+                .map(|(grade, count)| Challenge::GradeSends(grade.clone(), count)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TeamError {
+    Import(String, ArchiveError),
+    StorageFull,
+}
+
+impl TeamError {
+    fn message(&self, locale: Locale) -> String {
+        match self {
+            TeamError::Import(file_name, error) => {
+                format!("{}: {}", file_name, error.message(locale))
+            }
+            TeamError::StorageFull => locale.t(Text::TeamStorageFull).to_string(),
+        }
+    }
+}
+
+/// Reads a teammate's export: the JSON export, or an archive, decrypted with `passphrase` if
+/// it's encrypted.
+pub fn parse_export(
+    file_name: &str,
+    bytes: &[u8],
+    passphrase: &str,
+) -> Result<Teammate, TeamError> {
+    let error = |error| TeamError::Import(file_name.to_string(), error);
+
+    let decrypted;
+    let bytes = if encryption::is_encrypted(bytes) {
+        decrypted = encryption::decrypt(bytes, passphrase)
+            .map_err(|e| error(ArchiveError::Encryption(e)))?;
+        decrypted.as_slice()
+    } else {
+        bytes
+    };
+
+    let json = if bytes.starts_with(b"PK") {
+        archive::read(bytes).map_err(error)?.data_json
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    };
+
+    let data: PersistedData =
+        serde_json::from_str(&json).map_err(|_| error(ArchiveError::BadData))?;
+
+    // A single climber's name beats whatever the file was called.
+    let name = match data.settings.climbers.values().exactly_one() {
+        Ok(climber) => climber.name.clone(),
+        Err(_) => file_name.split('.').next().unwrap_or(file_name).to_string(),
+    };
+
+    Ok(Teammate {
+        name,
+        imported_at: Utc::now().timestamp(),
+        data,
+    })
+}
+
+/// One climber's ticks in one dataset.
+struct Entry<'a> {
+    name: String,
+    data: &'a PersistedData,
+    /// Set when the dataset has several climbers.
+    climber: Option<&'a String>,
+}
+
+impl<'a> Entry<'a> {
+    fn ticks(&self) -> impl Iterator<Item = (&'a Route, &'a Tick)> + '_ {
+        let settings = &self.data.settings;
+        let climber = self.climber;
+
+        self.data
+            .routes
+            .values()
+            .flat_map(|route| route.ticks.iter().map(move |tick| (route, tick)))
+            .filter(move |(_route, tick)| {
+                climber.is_none() || settings.tick_climber(tick) == climber
+            })
+    }
+
+    fn sends(&self) -> impl Iterator<Item = (&'a Route, &'a Tick)> + '_ {
+        self.ticks()
+            .filter(|(_route, tick)| matches!(tick.typ, TickType::Ascent))
+    }
+
+    fn in_this_month(&self, tick: &Tick) -> bool {
+        let settings = &self.data.settings;
+        let today = settings.today();
+        let day = settings.climbing_day(tick.timestamp);
+
+        day.year() == today.year() && day.month() == today.month()
+    }
+
+    fn standing(&self) -> Standing {
+        let mut standing = Standing::default();
+
+        for (route, tick) in self
+            .sends()
+            .filter(|(_route, tick)| self.in_this_month(tick))
+        {
+            standing.points += grade_points(&self.data.settings, &route.grade);
+            standing.sends += 1;
+            if tick.is_flash() {
+                standing.flashes += 1;
+            }
+        }
+
+        standing
+    }
+
+    fn has_sent(&self, route: &Route) -> bool {
+        let settings = &self.data.settings;
+
+        route.ticks.iter().any(|tick| {
+            matches!(tick.typ, TickType::Ascent)
+                && (self.climber.is_none() || settings.tick_climber(tick) == self.climber)
+        })
+    }
+
+    /// Progress towards a challenge, as done and needed.
+    fn progress(&self, challenge: &Challenge) -> (u32, u32) {
+        match challenge {
+            Challenge::SectionSweep(section) => {
+                let routes = self
+                    .data
+                    .routes
+                    .values()
+                    .filter(|route| !route.retired && route.section == *section);
+                let (sent, total) = routes.fold((0, 0), |(sent, total), route| {
+                    (sent + self.has_sent(route) as u32, total + 1)
+                });
+
+                (sent, total)
+            }
+            Challenge::MonthlySends(count) => {
+                let sends = self
+                    .sends()
+                    .filter(|(_route, tick)| self.in_this_month(tick))
+                    .count() as u32;
+
+                (sends.min(*count), *count)
+            }
+            Challenge::GradeSends(grade, count) => {
+                let sends = self
+                    .sends()
+                    .filter(|(route, _tick)| route.grade == *grade)
+                    .count() as u32;
+
+                (sends.min(*count), *count)
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Standing {
+    points: u32,
+    sends: u32,
+    flashes: u32,
+}
+
+/// A send is worth its grade's place in its grade system, so the easiest grade is one point.
+fn grade_points(settings: &Settings, grade: &str) -> u32 {
+    let group = match settings.grades.get(grade) {
+        Some(grade) => &grade.group,
+        None => return 0,
+    };
+
+    settings
+        .grades
+        .iter()
+        .filter(|(_k, g)| g.group == *group)
+        .sorted_by_key(|(_k, g)| g.sort)
+        .position(|(key, _g)| key == grade)
+        .map_or(0, |rank| rank as u32 + 1)
+}
+
+fn entries<'a>(
+    persisted: &'a PersistedData,
+    team: &'a [Teammate],
+    locale: Locale,
+) -> Vec<Entry<'a>> {
+    let datasets = Some((locale.t(Text::You).to_string(), persisted))
+        .into_iter()
+        .chain(
+            team.iter()
+                .map(|teammate| (teammate.name.clone(), &teammate.data)),
+        );
+
+    datasets
+        .flat_map(|(name, data)| {
+            if data.settings.climbers.len() < 2 {
+                vec![Entry {
+                    name,
+                    data,
+                    climber: None,
+                }]
+            } else {
+                data.settings
+                    .climbers
+                    .iter()
+                    .map(|(key, climber)| Entry {
+                        name: climber.name.clone(),
+                        data,
+                        climber: Some(key),
+                    })
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+/// The way to the team page from the stats page.
+pub fn view_team_link(url: Url, teammates: usize, locale: Locale) -> Node<Msg> {
+    div![
+        C!["main card"],
+        div![
+            C!["card-header"],
+            a![
+                C!["btn", "float-right"],
+                attrs! {
+                    At::Href => url
+                },
+                locale.t(Text::OpenTeam)
+            ],
+            div![C!["h5", "card-title"], locale.t(Text::Team)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.fill(Text::TeamCaption, &[&teammates])
+            ],
+        ],
+    ]
+}
+
+pub fn view_team(
+    persisted: &PersistedData,
+    team: &[Teammate],
+    draft: &ChallengeDraft,
+    passphrase: &str,
+    error: Option<&TeamError>,
+) -> Vec<Node<Msg>> {
+    let settings = &persisted.settings;
+    let locale = settings.locale();
+    let entries = entries(persisted, team, locale);
+
+    vec![
+        view_leaderboard(&entries, locale),
+        view_challenges(&persisted.challenges, &entries, draft, settings),
+        view_teammates(team, passphrase, error, locale),
+    ]
+}
+
+fn view_leaderboard(entries: &[Entry], locale: Locale) -> Node<Msg> {
+    let standings = entries
+        .iter()
+        .map(|entry| (entry, entry.standing()))
+        .sorted_by_key(|(_entry, standing)| {
+            (
+                std::cmp::Reverse(standing.points),
+                std::cmp::Reverse(standing.sends),
+            )
+        });
+
+    div![
+        C!["main card", "leaderboard"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Leaderboard)],
+            div![C!["card-subtitle", "text-gray"], locale.t(Text::ThisMonth)],
+        ],
+        div![
+            C!["card-body"],
+            table![
+                C!["table"],
+                thead![tr![
+                    th![],
+                    th![locale.t(Text::Climber)],
+                    th![locale.t(Text::Points)],
+                    th![locale.t(Text::Sends)],
+                    th![locale.t(Text::Flashes)],
+                ]],
+                tbody![standings
+                    .enumerate()
+                    .map(|(place, (entry, standing))| tr![
+                        td![format!("{}", place + 1)],
+                        td![entry.name.as_str()],
+                        td![format!("{}", standing.points)],
+                        td![format!("{}", standing.sends)],
+                        td![format!("{}", standing.flashes)],
+                    ])
+                    .collect::<Vec<Node<Msg>>>()]
+            ]
+        ]
+    ]
+}
+
+fn view_challenges(
+    challenges: &[Challenge],
+    entries: &[Entry],
+    draft: &ChallengeDraft,
+    settings: &Settings,
+) -> Node<Msg> {
+    let locale = settings.locale();
+
+    div![
+        C!["main card", "challenges"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Challenges)]
+        ],
+        div![
+            C!["card-body"],
+            challenges
+                .iter()
+                .enumerate()
+                .map(|(index, challenge)| {
+                    div![
+                        C!["challenge"],
+                        div![
+                            C!["challenge-title"],
+                            challenge.label(locale),
+                            button![
+                                C!["btn", "btn-clear", "float-right"],
+                                attrs! {
+                                    At::AriaLabel => locale.t(Text::Delete);
+                                },
+                                ev(Ev::Click, move |_| Msg::RemoveChallenge(index)),
+                            ],
+                        ],
+                        entries
+                            .iter()
+                            .map(|entry| {
+                                let (done, needed) = entry.progress(challenge);
+
+                                div![
+                                    C![
+                                        "challenge-progress",
+                                        IF!(needed > 0 && done >= needed => "done")
+                                    ],
+                                    span![entry.name.as_str()],
+                                    progress![
+                                        C!["progress"],
+                                        attrs! {
+                                            At::Value => done;
+                                            At::Max => needed.max(1);
+                                        }
+                                    ],
+                                    small![format!("{}/{}", done, needed)],
+                                ]
+                            })
+                            .collect::<Vec<Node<Msg>>>(),
+                    ]
+                })
+                .collect::<Vec<Node<Msg>>>(),
+            view_challenge_form(draft, settings),
+        ]
+    ]
+}
+
+fn view_challenge_form(draft: &ChallengeDraft, settings: &Settings) -> Node<Msg> {
+    let locale = settings.locale();
+
+    div![
+        C!["input-group", "challenge-form"],
+        select![
+            C!["form-select"],
+            ChallengeKind::ALL
+                .iter()
+                .enumerate()
+                .map(|(index, kind)| option![
+                    attrs! {
+                        At::Value => index;
+                        At::Selected => (draft.kind == *kind).as_at_value();
+                    },
+                    locale.t(kind.label())
+                ])
+                .collect::<Vec<Node<Msg>>>(),
+            input_ev(Ev::Change, |value| {
+                value
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| ChallengeKind::ALL.get(index).copied())
+                    .map(Msg::ChooseChallengeKind)
+            }),
+        ],
+        match draft.kind {
+            ChallengeKind::SectionSweep => select![
+                C!["form-select"],
+                settings
+                    .sections
+                    .keys()
+                    .map(|key| option![
+                        attrs! {
+                            At::Value => key;
+                            At::Selected => (draft.section == *key).as_at_value();
+                        },
+                        key
+                    ])
+                    .collect::<Vec<Node<Msg>>>(),
+                input_ev(Ev::Change, Msg::ChallengeSectionChanged),
+            ],
+            ChallengeKind::MonthlySends | ChallengeKind::GradeSends => input![
+                C!["form-input"],
+                attrs! {
+                    At::Type => "number";
+                    At::Min => 1;
+                    At::Value => draft.count;
+                },
+                input_ev(Ev::Input, Msg::ChallengeCountChanged),
+            ],
+        },
+        IF!(draft.kind == ChallengeKind::GradeSends => select![
+            C!["form-select"],
+            settings
+                .grades
+                .keys()
+                .map(|key| option![
+                    attrs! {
+                        At::Value => key;
+                        At::Selected => (draft.grade == *key).as_at_value();
+                    },
+                    key
+                ])
+                .collect::<Vec<Node<Msg>>>(),
+            input_ev(Ev::Change, Msg::ChallengeGradeChanged),
+        ]),
+        button![
+            C!["btn", "btn-primary", "input-group-btn"],
+            ev(Ev::Click, |_| Msg::AddChallenge),
+            locale.t(Text::AddChallenge)
+        ],
+    ]
+}
+
+fn view_teammates(
+    team: &[Teammate],
+    passphrase: &str,
+    error: Option<&TeamError>,
+    locale: Locale,
+) -> Node<Msg> {
+    let now = Utc::now();
+
+    div![
+        C!["main card"],
+        div![
+            C!["card-header"],
+            div![C!["h5", "card-title"], locale.t(Text::Teammates)],
+            div![
+                C!["card-subtitle", "text-gray"],
+                locale.t(Text::TeammatesCaption)
+            ],
+        ],
+        IF!(!team.is_empty() => ul![
+            C!["route-list"],
+            team.iter()
+                .enumerate()
+                .map(|(index, teammate)| li![div![
+                    C!["view"],
+                    label![
+                        teammate.name.as_str(),
+                        " ",
+                        small![
+                            C!["text-gray"],
+                            locale.fill(
+                                Text::TeammateImported,
+                                &[&util::time_diff_in_words(
+                                    Utc.timestamp_opt(teammate.imported_at, 0)
+                                        .single()
+                                        .unwrap_or_default(),
+                                    now,
+                                    locale
+                                )]
+                            )
+                        ],
+                    ],
+                    button![
+                        C!["tick-button btn btn-sm"],
+                        ev(Ev::Click, move |_| Msg::RemoveTeammate(index)),
+                        locale.t(Text::Delete)
+                    ],
+                ]])
+                .collect::<Vec<Node<Msg>>>()
+        ]),
+        div![
+            C!["card-body"],
+            div![
+                C!["form-group"],
+                label![C!["form-label"], locale.t(Text::Passphrase)],
+                input![
+                    C!["form-input"],
+                    attrs! {
+                        At::Type => "password";
+                        At::Value => passphrase;
+                        At::Placeholder => locale.t(Text::TeammatesPassphrasePlaceholder);
+                        At::AutoComplete => "off";
+                    },
+                    input_ev(Ev::Input, Msg::TeamPassphraseChanged),
+                ],
+            ],
+            button![
+                C!["btn"],
+                ev(Ev::Click, |_| Msg::AddTeammates),
+                locale.t(Text::AddTeammates)
+            ],
+        ],
+        error.map(|error| div![
+            C!["card-footer"],
+            div![C!["toast", "toast-error"], error.message(locale)]
+        ]),
+    ]
+}
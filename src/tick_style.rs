@@ -0,0 +1,36 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::grade::Grade;
+
+/// Styles that make a send a flash. An onsight is a flash without even having seen beta.
+pub const FLASH_STYLES: [&str; 2] = ["onsight", "flash"];
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TickStyle {
+    pub group: String,
+    pub label: String,
+    pub sort: i32,
+    /// Grade groups (disciplines) this style can be used with. Empty means all of them.
+    #[serde(default)]
+    pub grade_groups: Vec<String>,
+}
+
+impl TickStyle {
+    pub fn defaults() -> IndexMap<String, TickStyle> {
+        indexmap! {
+            "onsight".into() => TickStyle { group: "A".into(), label: "onsight".into(), sort: 1, grade_groups: vec!["A".into()] },
+            "flash".into() => TickStyle { group: "A".into(), label: "flash".into(), sort: 2, grade_groups: vec![] },
+            "redpoint".into() => TickStyle { group: "A".into(), label: "redpoint".into(), sort: 3, grade_groups: vec!["A".into()] },
+            "repeat".into() => TickStyle { group: "A".into(), label: "repeat".into(), sort: 4, grade_groups: vec![] },
+            "lead".into() => TickStyle { group: "B".into(), label: "lead".into(), sort: 5, grade_groups: vec!["A".into()] },
+            "toprope".into() => TickStyle { group: "B".into(), label: "toprope".into(), sort: 6, grade_groups: vec!["A".into()] },
+            "autobelay".into() => TickStyle { group: "B".into(), label: "autobelay".into(), sort: 7, grade_groups: vec!["A".into()] },
+        }
+    }
+
+    pub fn applies_to(&self, grade: Option<&Grade>) -> bool {
+        self.grade_groups.is_empty()
+            || grade.is_some_and(|grade| self.grade_groups.contains(&grade.group))
+    }
+}
@@ -1,6 +1,9 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
 
-pub fn time_diff_in_words(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+use crate::i18n::{Locale, Text};
+
+/// A compact, localised description of how long ago `time` was, e.g. "3h" or "2w".
+pub fn time_diff_in_words(time: DateTime<Utc>, now: DateTime<Utc>, locale: Locale) -> String {
     let diff = now.signed_duration_since(time);
 
     let minutes = diff.num_minutes();
@@ -8,18 +11,157 @@ pub fn time_diff_in_words(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
     let days = diff.num_days();
 
     if minutes < 1 {
-        "now".to_string()
-    } else if minutes == 1 {
-        "1m".to_string()
+        locale.t(Text::Now).to_string()
     } else if minutes < 60 {
-        format!("{}m", minutes)
-    } else if hours == 1 {
-        "1h".to_string()
+        locale.fill(Text::Minutes, &[&minutes])
     } else if hours < 24 {
-        format!("{}h", hours)
-    } else if days == 1 {
-        "1d".to_string()
+        locale.fill(Text::Hours, &[&hours])
+    } else if days < 7 {
+        locale.fill(Text::Days, &[&days])
+    } else if days < 30 {
+        locale.fill(Text::Weeks, &[&(days / 7)])
+    } else if days < 365 {
+        locale.fill(Text::Months, &[&(days / 30)])
     } else {
-        format!("{}d", days)
+        locale.fill(Text::Years, &[&(days / 365)])
+    }
+}
+
+/// The climbing day a timestamp belongs to. Climbing days start at `cutover_hour` local time
+/// rather than midnight, so a session that runs late stays on the day it started. Timestamps
+/// chrono can't represent fall on the epoch.
+pub fn climbing_day<Tz: TimeZone>(timestamp: i64, tz: &Tz, cutover_hour: u32) -> NaiveDate {
+    tz.timestamp_opt(timestamp, 0)
+        .earliest()
+        .map(|time| (time.naive_local() - Duration::hours(cutover_hour.into())).date())
+        .unwrap_or_default()
+}
+
+/// The timestamp at which a climbing day starts.
+pub fn climbing_day_start<Tz: TimeZone>(date: NaiveDate, tz: &Tz, cutover_hour: u32) -> i64 {
+    let start = date.and_time(NaiveTime::MIN) + Duration::hours(cutover_hour.into());
+
+    // If the cutover falls in a DST gap that local time never happens, so walk forward to the
+    // first minute that does.
+    (0..24 * 60)
+        .map(|minutes| start + Duration::minutes(minutes))
+        .find_map(|local| tz.from_local_datetime(&local).earliest())
+        .map_or_else(|| start.and_utc().timestamp(), |start| start.timestamp())
+}
+
+/// The first day of the week containing `date`.
+pub fn week_start(date: NaiveDate, first_day: Weekday) -> NaiveDate {
+    let days_into_week =
+        (7 + date.weekday().num_days_from_monday() - first_day.num_days_from_monday()) % 7;
+
+    date - Duration::days(days_into_week.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+    use chrono_tz::Europe::Berlin;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> i64 {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, second)
+            .unwrap()
+            .timestamp()
+    }
+
+    #[test]
+    fn climbing_day_in_fixed_offset() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+
+        // 01:30 local belongs to the previous day with a 4am cutover, 04:00 to the new one.
+        assert_eq!(
+            climbing_day(utc(2024, 3, 9, 23, 30, 0), &tz, 4),
+            date(2024, 3, 9)
+        );
+        assert_eq!(
+            climbing_day(utc(2024, 3, 10, 1, 59, 59), &tz, 4),
+            date(2024, 3, 9)
+        );
+        assert_eq!(
+            climbing_day(utc(2024, 3, 10, 2, 0, 0), &tz, 4),
+            date(2024, 3, 10)
+        );
+        assert_eq!(
+            climbing_day(utc(2024, 3, 10, 2, 0, 0), &tz, 0),
+            date(2024, 3, 10)
+        );
+    }
+
+    #[test]
+    fn climbing_day_start_in_fixed_offset() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+
+        assert_eq!(
+            climbing_day_start(date(2024, 3, 10), &tz, 4),
+            utc(2024, 3, 10, 2, 0, 0)
+        );
+        assert_eq!(
+            climbing_day_start(date(2024, 3, 10), &tz, 0),
+            utc(2024, 3, 9, 22, 0, 0)
+        );
+    }
+
+    #[test]
+    fn spring_forward_with_cutover_in_skipped_hour() {
+        // Berlin skips 02:00-03:00 on 2024-03-31, so the day starts at 03:00 CEST.
+        let start = climbing_day_start(date(2024, 3, 31), &Berlin, 2);
+        assert_eq!(start, utc(2024, 3, 31, 1, 0, 0));
+
+        assert_eq!(climbing_day(start, &Berlin, 2), date(2024, 3, 31));
+        assert_eq!(climbing_day(start - 1, &Berlin, 2), date(2024, 3, 30));
+        // The day before is a normal one, starting at 02:00 CET.
+        assert_eq!(
+            climbing_day_start(date(2024, 3, 30), &Berlin, 2),
+            utc(2024, 3, 30, 1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn fall_back_with_cutover_in_repeated_hour() {
+        // Berlin repeats 02:00-03:00 on 2024-10-27, and the day starts at the first 02:00.
+        let start = climbing_day_start(date(2024, 10, 27), &Berlin, 2);
+        assert_eq!(start, utc(2024, 10, 27, 0, 0, 0));
+
+        assert_eq!(climbing_day(start - 1, &Berlin, 2), date(2024, 10, 26));
+        assert_eq!(climbing_day(start, &Berlin, 2), date(2024, 10, 27));
+        // 02:30 the second time round is still the same day.
+        assert_eq!(
+            climbing_day(utc(2024, 10, 27, 1, 30, 0), &Berlin, 2),
+            date(2024, 10, 27)
+        );
+        // The next day starts at 02:00 CET.
+        assert_eq!(
+            climbing_day_start(date(2024, 10, 28), &Berlin, 2),
+            utc(2024, 10, 28, 1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn week_start_for_every_first_day() {
+        // A Wednesday.
+        let wednesday = date(2024, 1, 10);
+        let expected = [
+            (Weekday::Mon, date(2024, 1, 8)),
+            (Weekday::Tue, date(2024, 1, 9)),
+            (Weekday::Wed, date(2024, 1, 10)),
+            (Weekday::Thu, date(2024, 1, 4)),
+            (Weekday::Fri, date(2024, 1, 5)),
+            (Weekday::Sat, date(2024, 1, 6)),
+            (Weekday::Sun, date(2024, 1, 7)),
+        ];
+
+        for (first_day, start) in expected.iter() {
+            assert_eq!(week_start(wednesday, *first_day), *start, "{:?}", first_day);
+            assert_eq!(week_start(*start, *first_day), *start, "{:?}", first_day);
+        }
     }
 }